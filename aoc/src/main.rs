@@ -0,0 +1,7 @@
+use aoclib::AOC;
+
+fn main() {
+    let aoc = AOC::new().year(aoc2019::year()).year(aoc2020::year());
+
+    aoclib::cli::run(aoc);
+}
@@ -1,6 +1,6 @@
 use std::convert::Infallible;
 
-pub fn part1(input: String) -> Result<u32, Infallible>
+pub fn part1(input: &str) -> Result<u32, Infallible>
 {
     let total = parse_input(input)
         .into_iter()
@@ -9,7 +9,7 @@ pub fn part1(input: String) -> Result<u32, Infallible>
     Ok(total)
 }
 
-pub fn part2(input: String) -> Result<u32, Infallible>
+pub fn part2(input: &str) -> Result<u32, Infallible>
 {
     let total = parse_input(input)
         .into_iter()
@@ -33,7 +33,7 @@ fn calc_fuel_recurse(weight: u32) -> u32 {
     fuel + calc_fuel_recurse(fuel)
 }
 
-fn parse_input(input: String) -> Vec<u32>
+fn parse_input(input: &str) -> Vec<u32>
 {
     input
         .lines()
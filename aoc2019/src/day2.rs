@@ -1,5 +1,7 @@
 use std::convert::Infallible;
 
+use aoclib::Cancel;
+
 fn run_intcode(mut prog: Vec<u32>) -> Option<u32>
 {
     for i in (0..prog.len()).step_by(4) {
@@ -24,7 +26,7 @@ fn run_intcode(mut prog: Vec<u32>) -> Option<u32>
     prog.get(0).map(|&x| x)
 }
 
-pub fn part1(input: String) -> Result<u32, Infallible>
+pub fn part1(input: &str) -> Result<u32, Infallible>
 {
     let mut prog = parse_input(input);
 
@@ -34,11 +36,12 @@ pub fn part1(input: String) -> Result<u32, Infallible>
     return Ok(run_intcode(prog).unwrap());
 }
 
-pub fn part2(input: String) -> Result<u32, Infallible>
+pub fn part2(input: &str, cancel: &Cancel) -> anyhow::Result<u32>
 {
     let prog = parse_input(input);
 
     for x in 0..prog.len() {
+        cancel.check()?;
         for y in 0..prog.len() {
             let mut prog = prog.clone();
             prog[1] = x as u32;
@@ -54,7 +57,7 @@ pub fn part2(input: String) -> Result<u32, Infallible>
     panic!("Solution not found")
 }
 
-fn parse_input(input: String) -> Vec<u32>
+fn parse_input(input: &str) -> Vec<u32>
 {
     input
         .split(',')
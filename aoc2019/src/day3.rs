@@ -1,8 +1,12 @@
 use std::collections::{HashSet, HashMap};
 
+use std::convert::TryFrom;
+
 use anyhow::{anyhow, Result};
+use aoclib::geom::Point2;
+use aoclib::turtle::{Direction, Turtle};
 
-pub fn part1(input: String) -> Result<i32> {
+pub fn part1(input: &str) -> Result<i64> {
     let (wire1, wire2) = {
         let mut split = input.lines();
         split.next()
@@ -11,17 +15,18 @@ pub fn part1(input: String) -> Result<i32> {
             })
             .ok_or(anyhow!("input too short"))?
     };
+    let (wire1, wire2) = (parse_wire(wire1)?, parse_wire(wire2)?);
 
-    let points1: HashSet<(i32, i32)> = wire_points(wire1).collect();
-    let points2: HashSet<(i32, i32)> = wire_points(wire2).collect();
+    let points1: HashSet<Point2> = wire_points(&wire1).collect();
+    let points2: HashSet<Point2> = wire_points(&wire2).collect();
 
     points1.intersection(&points2)
-        .map(|&(x, y)| x.abs() + y.abs())
+        .map(|p| p.manhattan(&Point2::ORIGIN))
         .min()
         .ok_or(anyhow!("wires do not intersect"))
 }
 
-pub fn part2(input: String) -> Result<usize> {
+pub fn part2(input: &str) -> Result<usize> {
     let (wire1, wire2) = {
         let mut split = input.lines();
         split.next()
@@ -30,12 +35,13 @@ pub fn part2(input: String) -> Result<usize> {
             })
             .ok_or(anyhow!("input too short"))?
     };
+    let (wire1, wire2) = (parse_wire(wire1)?, parse_wire(wire2)?);
 
-    let points1: HashMap<(i32, i32), usize> = wire_points(wire1).enumerate().map(|(a, b)| (b, a)).collect();
+    let points1: HashMap<Point2, usize> = wire_points(&wire1).enumerate().map(|(a, b)| (b, a)).collect();
 
     let mut min = usize::MAX;
 
-    for (i, p) in wire_points(wire2).enumerate() {
+    for (i, p) in wire_points(&wire2).enumerate() {
         if let Some(steps) = points1.get(&p) {
             let distance = steps + i + 2;
             if distance < min {
@@ -46,31 +52,25 @@ pub fn part2(input: String) -> Result<usize> {
     Ok(min)
 }
 
-fn move_point(start: (i32, i32), dir: char, len: i32) -> (i32, i32) {
-    match dir {
-        'R' => (start.0 + len, start.1),
-        'L' => (start.0 - len, start.1),
-        'U' => (start.0, start.1 + len),
-        'D' => (start.0, start.1 - len),
-        _ => panic!("invalid direction"),
-    }
+/// The direction and length of each segment of a wire
+fn parse_wire(line: &str) -> Result<Vec<(Direction, usize)>> {
+    line.split(',')
+        .map(|motion| {
+            let mut chars = motion.chars();
+            let dir = chars.next().ok_or(anyhow!("empty motion"))?;
+            Ok((Direction::try_from(dir)?, chars.as_str().parse()?))
+        })
+        .collect()
 }
 
-fn wire_points<'a>(line: &'a str) -> impl Iterator<Item = (i32, i32)> + 'a {
-    line
-        .split(',')
-        .scan((0, 0), |state, motion| {
-            let (dir, len) = motion.split_at(1);
-            let dir = dir.chars().nth(0).unwrap();
-            let len = len.parse::<i32>().unwrap();
-
-            let start = *state;
-
-            *state = move_point(start, dir, len);
-
-            Some((1..=len).map(move |l| move_point(start, dir, l)))
+fn wire_points(wire: &[(Direction, usize)]) -> impl Iterator<Item = Point2> + '_ {
+    let mut turtle = Turtle::new(Direction::North);
+    wire.iter()
+        .flat_map(|&(dir, len)| std::iter::repeat(dir).take(len))
+        .map(move |dir| {
+            turtle.step(dir, 1);
+            turtle.position
         })
-        .flatten()
 }
 
 #[cfg(test)]
@@ -86,25 +86,31 @@ mod test {
             (2, 1), (1, 1),
             (1, 0),
         ];
+        let points: Vec<Point2> = points.into_iter().map(Point2::from).collect();
 
-        assert_eq!(points, wire_points(input).collect::<Vec<(i32, i32)>>());
+        assert_eq!(points, wire_points(&parse_wire(input).unwrap()).collect::<Vec<Point2>>());
     }
 
     const EXAMPLE1: &str = "R8,U5,L5,D3\nU7,R6,D4,L4";
     const EXAMPLE2: &str = "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83";
     const EXAMPLE3: &str = "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7";
 
+    #[test]
+    fn test_invalid_wire() {
+        assert_eq!("unknown direction \"X\"", part1("R3,X1\nU1").unwrap_err().to_string());
+    }
+
     #[test]
     fn part1_examples() {
-        assert_eq!(6, part1(EXAMPLE1.to_string()).unwrap());
-        assert_eq!(159, part1(EXAMPLE2.to_string()).unwrap());
-        assert_eq!(135, part1(EXAMPLE3.to_string()).unwrap());
+        assert_eq!(6, part1(EXAMPLE1).unwrap());
+        assert_eq!(159, part1(EXAMPLE2).unwrap());
+        assert_eq!(135, part1(EXAMPLE3).unwrap());
     }
 
     #[test]
     fn part2_examples() {
-        assert_eq!(30, part2(EXAMPLE1.to_string()).unwrap());
-        assert_eq!(610, part2(EXAMPLE2.to_string()).unwrap());
-        assert_eq!(410, part2(EXAMPLE3.to_string()).unwrap());
+        assert_eq!(30, part2(EXAMPLE1).unwrap());
+        assert_eq!(610, part2(EXAMPLE2).unwrap());
+        assert_eq!(410, part2(EXAMPLE3).unwrap());
     }
 }
@@ -0,0 +1,14 @@
+#![allow(clippy::all)]
+
+use aoclib::{Day, Year};
+
+mod day1;
+mod day2;
+mod day3;
+
+pub fn year() -> Year {
+    Year::new(2019)
+        .day(Day::new(1).part_str(1, day1::part1).part_str(2, day1::part2))
+        .day(Day::new(2).part_str(1, day2::part1).part_cancellable(2, day2::part2))
+        .day(Day::new(3).part_str(1, day3::part1).part_str(2, day3::part2))
+}
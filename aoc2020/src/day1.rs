@@ -4,7 +4,7 @@ use std::convert::Infallible;
 
 use super::parse;
 
-pub fn part1(input: String) -> Result<u32, Infallible> {
+pub fn part1(input: &str) -> Result<u32, Infallible> {
     let nums: hash_set::HashSet<_> = parse::to_nums(input).into_iter().collect();
 
     for i in nums.iter().cloned() {
@@ -16,7 +16,7 @@ pub fn part1(input: String) -> Result<u32, Infallible> {
     panic!("No two items add up to 2020!");
 }
 
-pub fn part2(input: String) -> Result<u32, Infallible> {
+pub fn part2(input: &str) -> Result<u32, Infallible> {
     let nums = parse::to_nums(input);
 
     for (a, b, c) in nums.into_iter().tuple_combinations() {
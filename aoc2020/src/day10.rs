@@ -4,7 +4,7 @@ use anyhow::Result;
 
 use super::parse::to_nums;
 
-pub fn part1(input: String) -> Result<usize> {
+pub fn part1(input: &str) -> Result<usize> {
     let adapters = {
         let mut a = to_nums(input);
         a.sort_unstable();
@@ -30,7 +30,7 @@ pub fn part1(input: String) -> Result<usize> {
     Ok(ones * threes)
 }
 
-pub fn part2(input: String) -> Result<usize> {
+pub fn part2(input: &str) -> Result<usize> {
     let adapters = {
         let mut a = to_nums(input);
         a.sort_unstable();
@@ -112,13 +112,13 @@ mod test {
 
     #[test]
     fn part1_examples() {
-        assert_eq!(35, part1(SMALL_EXAMPLE.to_string()).unwrap());
-        assert_eq!(220, part1(LARGE_EXAMPLE.to_string()).unwrap());
+        assert_eq!(35, part1(SMALL_EXAMPLE).unwrap());
+        assert_eq!(220, part1(LARGE_EXAMPLE).unwrap());
     }
 
     #[test]
     fn part2_examples() {
-        assert_eq!(8, part2(SMALL_EXAMPLE.to_string()).unwrap());
-        assert_eq!(19208, part2(LARGE_EXAMPLE.to_string()).unwrap());
+        assert_eq!(8, part2(SMALL_EXAMPLE).unwrap());
+        assert_eq!(19208, part2(LARGE_EXAMPLE).unwrap());
     }
 }
@@ -1,181 +1,52 @@
-use std::ops::{Add, AddAssign, Mul, MulAssign};
+use std::convert::TryFrom;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
+use aoclib::geom::Point2;
+use aoclib::turtle::{self, Direction, Turtle};
 
-pub fn part1(input: String) -> Result<i32> {
-    let mut ferry = Ferry::new();
+pub fn part1(input: &str) -> Result<i64> {
+    let mut ferry = Turtle::new(Direction::East);
 
     for instruction in input.lines() {
-        let (action, value) = instruction.split_at(1);
-        let action = action.chars().next().unwrap();
-        let value = value.parse::<i32>()?;
+        let (action, value) = parse_instruction(instruction)?;
 
-        ferry = match action {
-            'L' => ferry.turn_ferry(value),
-            'R' => ferry.turn_ferry(-value),
+        match action {
+            'L' => ferry.turn(-value)?,
+            'R' => ferry.turn(value)?,
             'F' => ferry.forward(value),
-            'N' => ferry.move_ferry(NORTH * value),
-            'E' => ferry.move_ferry(EAST * value),
-            'S' => ferry.move_ferry(SOUTH * value),
-            'W' => ferry.move_ferry(WEST * value),
-            _ => return Err(anyhow!("unknown action '{}'", action)),
-        };
-    }
-
-    let pos = ferry.position;
-    Ok(pos.x.abs() + pos.y.abs())
-}
-
-pub fn part2(input: String) -> Result<i32> {
-    let mut ferry = Ferry::new();
-
-    for instruction in input.lines() {
-        let (action, value) = instruction.split_at(1);
-        let action = action.chars().next().unwrap();
-        let value = value.parse::<i32>()?;
-
-        ferry = match action {
-            'L' => ferry.turn_waypoint(value),
-            'R' => ferry.turn_waypoint(-value),
-            'F' => ferry.go_to_waypoint(value),
-            'N' => ferry.move_waypoint(NORTH * value),
-            'E' => ferry.move_waypoint(EAST * value),
-            'S' => ferry.move_waypoint(SOUTH * value),
-            'W' => ferry.move_waypoint(WEST * value),
-            _ => return Err(anyhow!("unknown action '{}'", action)),
-        };
-    }
-
-    let pos = ferry.position;
-    Ok(pos.x.abs() + pos.y.abs())
-}
-
-const NORTH: Coord = Coord::new(0, 1);
-const EAST: Coord = Coord::new(1, 0);
-const SOUTH: Coord = Coord::new(0, -1);
-const WEST: Coord = Coord::new(-1, 0);
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Ferry {
-    direction: i32,
-    position: Coord,
-    waypoint: Coord,
-}
-
-impl Ferry {
-    fn new() -> Self {
-        Ferry {
-            direction: 0,
-            position: Coord::new(0, 0),
-            waypoint: Coord::new(10, 1),
-        }
-    }
-
-    fn forward(self, value: i32) -> Self {
-        match self.direction {
-            0 => self.move_ferry(EAST * value),
-            90 => self.move_ferry(NORTH * value),
-            180 => self.move_ferry(WEST * value),
-            270 => self.move_ferry(SOUTH * value),
-            _ => panic!("I refuse to do trigonometry..."),
-        }
-    }
-
-    fn turn_ferry(self, value: i32) -> Self {
-        let newdir = (self.direction + value).rem_euclid(360);
-        Ferry {
-            direction: newdir,
-            position: self.position,
-            waypoint: self.waypoint,
-        }
-    }
-
-    fn move_ferry(self, translation: Coord) -> Self {
-        Ferry {
-            direction: self.direction,
-            position: self.position + translation,
-            waypoint: self.waypoint,
-        }
-    }
-
-    fn turn_waypoint(self, dir: i32) -> Self {
-        let dir = dir.rem_euclid(360);
-        let new_waypoint = self.waypoint.turn(dir);
-        Ferry {
-            direction: self.direction,
-            position: self.position,
-            waypoint: new_waypoint,
-        }
-    }
-
-    fn move_waypoint(self, translation: Coord) -> Self {
-        Ferry {
-            direction: self.direction,
-            position: self.position,
-            waypoint: self.waypoint + translation,
+            'N' | 'E' | 'S' | 'W' => ferry.step(Direction::try_from(action)?, value),
+            _ => bail!("unknown action '{}'", action),
         }
     }
 
-    fn go_to_waypoint(self, value: i32) -> Self {
-        let translation = self.waypoint * value;
-        self.move_ferry(translation)
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl Coord {
-    const fn new(x: i32, y: i32) -> Self {
-        Coord { x, y }
-    }
-
-    fn turn(self, dir: i32) -> Self {
-        match dir {
-            0 => self,
-            90 => Coord::new(-self.y, self.x),
-            180 => Coord::new(-self.x, -self.y),
-            270 => Coord::new(self.y, -self.x),
-            _ => panic!("NEVER TRIG!"),
-        }
-    }
+    Ok(ferry.position.manhattan(&Point2::ORIGIN))
 }
 
-impl Add for Coord {
-    type Output = Self;
+pub fn part2(input: &str) -> Result<i64> {
+    let mut position = Point2::ORIGIN;
+    let mut waypoint = Point2::new([10, 1]);
 
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
+    for instruction in input.lines() {
+        let (action, value) = parse_instruction(instruction)?;
+
+        match action {
+            'L' => waypoint = turtle::rotate(waypoint, -value)?,
+            'R' => waypoint = turtle::rotate(waypoint, value)?,
+            'F' => position += waypoint * value,
+            'N' | 'E' | 'S' | 'W' => waypoint += Direction::try_from(action)?.delta() * value,
+            _ => bail!("unknown action '{}'", action),
         }
     }
-}
-
-impl AddAssign for Coord {
-    fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
-    }
-}
-
-impl Mul<i32> for Coord {
-    type Output = Self;
 
-    fn mul(self, other: i32) -> Self {
-        Self {
-            x: self.x * other,
-            y: self.y * other,
-        }
-    }
+    Ok(position.manhattan(&Point2::ORIGIN))
 }
 
-impl MulAssign<i32> for Coord {
-    fn mul_assign(&mut self, other: i32) {
-        *self = *self * other;
-    }
+/// Split an instruction like "F10" into its action and value
+fn parse_instruction(instruction: &str) -> Result<(char, i64)> {
+    let mut chars = instruction.chars();
+    let action = chars.next().ok_or_else(|| anyhow!("empty instruction"))?;
+    let value = chars.as_str().parse::<i64>()?;
+    Ok((action, value))
 }
 
 #[cfg(test)]
@@ -186,11 +57,24 @@ mod test {
 
     #[test]
     fn part1_example() {
-        assert_eq!(25, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(25, part1(EXAMPLE).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(286, part2(EXAMPLE.to_string()).unwrap());
+        assert_eq!(286, part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn invalid_turns() {
+        assert_eq!(
+            "can't turn 45 degrees, only multiples of 90",
+            part1("F10\nR45").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "can't turn -100 degrees, only multiples of 90",
+            part2("L100").unwrap_err().to_string()
+        );
+        assert_eq!("unknown action 'X'", part1("X1").unwrap_err().to_string());
     }
 }
@@ -3,8 +3,8 @@ use aoclib::strtools;
 use num_bigint::BigUint;
 use num_integer::Integer;
 
-pub fn part1(input: String) -> Result<u32> {
-    let (time, buses) = strtools::split_once(input.as_str(), "\n");
+pub fn part1(input: &str) -> Result<u32> {
+    let (time, buses) = strtools::split_once(input, "\n");
     let time = time.parse::<u32>()?;
     let buses: Vec<u32> = buses.trim()
         .split(',')
@@ -21,8 +21,8 @@ pub fn part1(input: String) -> Result<u32> {
     Ok(id * wait)
 }
 
-pub fn part2(input: String) -> Result<BigUint> {
-    let (_, buses) = strtools::split_once(input.as_str(), "\n");
+pub fn part2(input: &str) -> Result<BigUint> {
+    let (_, buses) = strtools::split_once(input, "\n");
     let buses: Vec<(BigUint, BigUint)> = buses.trim()
         .split(',')
         .enumerate()
@@ -62,11 +62,11 @@ mod test {
 
     #[test]
     fn part1_example() {
-        assert_eq!(295, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(295, part1(EXAMPLE).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(BigUint::from(1068781u32), part2(EXAMPLE.to_string()).unwrap());
+        assert_eq!(BigUint::from(1068781u32), part2(EXAMPLE).unwrap());
     }
 }
\ No newline at end of file
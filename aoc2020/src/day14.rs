@@ -6,7 +6,7 @@ use std::str::FromStr;
 use anyhow::{anyhow, Result};
 use aoclib::strtools;
 
-pub fn part1(input: String) -> Result<u64> {
+pub fn part1(input: &str) -> Result<u64> {
     let mut computer = Computer::new();
     for line in input.trim().lines() {
         let line = line.trim();
@@ -31,7 +31,7 @@ pub fn part1(input: String) -> Result<u64> {
     Ok(computer.mem_iter().map(|(&_loc, &value)| value).sum())
 }
 
-pub fn part2(input: String) -> Result<u64> {
+pub fn part2(input: &str) -> Result<u64> {
     let mut computer = Computer::new();
     for line in input.trim().lines() {
         let line = line.trim();
@@ -169,7 +169,7 @@ mod test {
 
     #[test]
     pub fn part1_example() {
-        assert_eq!(165, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(165, part1(EXAMPLE).unwrap());
     }
 
     const EXAMPLE2: &str = "
@@ -180,6 +180,6 @@ mod test {
 
     #[test]
     pub fn part2_example() {
-        assert_eq!(208, part2(EXAMPLE2.to_string()).unwrap());
+        assert_eq!(208, part2(EXAMPLE2).unwrap());
     }
 }
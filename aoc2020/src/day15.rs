@@ -1,14 +1,18 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use aoclib::Params;
+
+pub fn part1(input: &str, params: &Params) -> Result<usize> {
+    let turns: usize = params.get("turns")?;
+    ensure!(turns > 0, "turns must be at least 1");
 
-pub fn part1(input: String) -> Result<usize> {
     let mut nums: Vec<usize> = input.trim()
         .split(',')
         .map(|n| n.parse::<usize>())
         .collect::<Result<_, _>>()?;
 
-    while nums.len() < 2020 {
+    while nums.len() < turns {
         let last = *nums.last().unwrap();
         let pos = nums.iter().rev()
             .skip(1)
@@ -21,15 +25,22 @@ pub fn part1(input: String) -> Result<usize> {
         nums.push(next);
     }
 
-    Ok(*nums.last().unwrap())
+    Ok(nums[turns - 1])
 }
 
-pub fn part2(input: String) -> Result<usize> {
+pub fn part2(input: &str, params: &Params) -> Result<usize> {
+    let turns: usize = params.get("turns")?;
+    ensure!(turns > 0, "turns must be at least 1");
+
     let mut init: Vec<usize> = input.trim()
         .split(',')
         .map(|n| n.parse::<usize>())
         .collect::<Result<_, _>>()?;
 
+    if turns <= init.len() {
+        return Ok(init[turns - 1]);
+    }
+
     let mut position_map = HashMap::new();
     let mut last = init.pop().unwrap();
 
@@ -37,7 +48,7 @@ pub fn part2(input: String) -> Result<usize> {
         position_map.insert(*num, idx);
     }
 
-    for position in init.len()..29_999_999 {
+    for position in init.len()..turns - 1 {
         let last_pos = position_map.get(&last);
 
         let next = match last_pos {
@@ -64,26 +75,40 @@ mod test {
     const EXAMPLE6: &str = "3,2,1";
     const EXAMPLE7: &str = "3,1,2";
 
+    fn params(turns: usize) -> Params {
+        Params::new().set("turns", turns)
+    }
+
     #[test]
     fn part1_examples() {
-        assert_eq!(436,  part1(EXAMPLE1.to_string()).unwrap());
-        assert_eq!(1,    part1(EXAMPLE2.to_string()).unwrap());
-        assert_eq!(10,   part1(EXAMPLE3.to_string()).unwrap());
-        assert_eq!(27,   part1(EXAMPLE4.to_string()).unwrap());
-        assert_eq!(78,   part1(EXAMPLE5.to_string()).unwrap());
-        assert_eq!(438,  part1(EXAMPLE6.to_string()).unwrap());
-        assert_eq!(1836, part1(EXAMPLE7.to_string()).unwrap());
+        assert_eq!(436,  part1(EXAMPLE1, &params(2020)).unwrap());
+        assert_eq!(1,    part1(EXAMPLE2, &params(2020)).unwrap());
+        assert_eq!(10,   part1(EXAMPLE3, &params(2020)).unwrap());
+        assert_eq!(27,   part1(EXAMPLE4, &params(2020)).unwrap());
+        assert_eq!(78,   part1(EXAMPLE5, &params(2020)).unwrap());
+        assert_eq!(438,  part1(EXAMPLE6, &params(2020)).unwrap());
+        assert_eq!(1836, part1(EXAMPLE7, &params(2020)).unwrap());
+    }
+
+    #[test]
+    fn fewer_turns_than_starting_numbers() {
+        for &(turns, expected) in &[(1, 0), (2, 3), (3, 6), (4, 0), (5, 3)] {
+            assert_eq!(expected, part1(EXAMPLE1, &params(turns)).unwrap());
+            assert_eq!(expected, part2(EXAMPLE1, &params(turns)).unwrap());
+        }
+        assert!(part1(EXAMPLE1, &params(0)).is_err());
+        assert!(part2(EXAMPLE1, &params(0)).is_err());
     }
 
     #[test]
     #[ignore]
     fn part2_examples() {
-        assert_eq!(175594,  part2(EXAMPLE1.to_string()).unwrap());
-        assert_eq!(2578,    part2(EXAMPLE2.to_string()).unwrap());
-        assert_eq!(3544142, part2(EXAMPLE3.to_string()).unwrap());
-        assert_eq!(261214,  part2(EXAMPLE4.to_string()).unwrap());
-        assert_eq!(6895259, part2(EXAMPLE5.to_string()).unwrap());
-        assert_eq!(18,      part2(EXAMPLE6.to_string()).unwrap());
-        assert_eq!(362,     part2(EXAMPLE7.to_string()).unwrap());
+        assert_eq!(175594,  part2(EXAMPLE1, &params(30_000_000)).unwrap());
+        assert_eq!(2578,    part2(EXAMPLE2, &params(30_000_000)).unwrap());
+        assert_eq!(3544142, part2(EXAMPLE3, &params(30_000_000)).unwrap());
+        assert_eq!(261214,  part2(EXAMPLE4, &params(30_000_000)).unwrap());
+        assert_eq!(6895259, part2(EXAMPLE5, &params(30_000_000)).unwrap());
+        assert_eq!(18,      part2(EXAMPLE6, &params(30_000_000)).unwrap());
+        assert_eq!(362,     part2(EXAMPLE7, &params(30_000_000)).unwrap());
     }
 }
@@ -5,24 +5,23 @@ use anyhow::{anyhow, Result};
 use aoclib::strtools;
 use itertools::Itertools;
 
-pub fn part1(input: String) -> Result<usize> {
-    let (fields, _, tickets) = parse_input(input)?;
-
+pub fn part1(notes: &Notes) -> Result<usize> {
     let mut error_rate = 0;
-    for ticket in tickets {
-        error_rate += ticket.error_rate(&fields).unwrap_or(0);
+    for ticket in notes.nearby.iter() {
+        error_rate += ticket.error_rate(&notes.fields).unwrap_or(0);
     }
     Ok(error_rate)
 }
 
-pub fn part2(input: String) -> Result<usize> {
-    let (fields, mine, nearby) = parse_input(input)?;
-
-    let indexes = solve_fields(&fields, &nearby)?;
+pub fn part2(notes: &Notes) -> Result<usize> {
+    let fields = &notes.fields;
+    let indexes = solve_fields(fields, &notes.nearby)?;
 
-    let vals = mine
+    let vals = notes
+        .mine
         .0
-        .into_iter()
+        .iter()
+        .copied()
         .enumerate()
         .map(|(i, val)| (fields[indexes[i]].name.as_str(), val))
         .filter(|&(name, _val)| name.starts_with("departure"))
@@ -55,8 +54,8 @@ fn solve_single_field(options: &[HashSet<usize>]) -> Option<(usize, usize)> {
 fn solve_fields(fields: &[Field], tickets: &[Ticket]) -> Result<Vec<usize>> {
     let tickets: Vec<Ticket> = tickets
         .iter()
-        .cloned()
         .filter(|t| t.error_rate(fields).is_none())
+        .cloned()
         .collect();
 
     let mut options: Vec<HashSet<usize>> = vec![(0..fields.len()).collect(); fields.len()];
@@ -81,13 +80,19 @@ fn solve_fields(fields: &[Field], tickets: &[Ticket]) -> Result<Vec<usize>> {
         }
     }
 
-    if result.iter().any(|&field| field == usize::MAX) {
+    if result.contains(&usize::MAX) {
         return Err(anyhow!("no solution for given fields"));
     }
     Ok(result)
 }
 
-fn parse_input(input: String) -> Result<(Vec<Field>, Ticket, Vec<Ticket>)> {
+pub struct Notes {
+    fields: Vec<Field>,
+    mine: Ticket,
+    nearby: Vec<Ticket>,
+}
+
+pub fn parse(input: &str) -> Result<Notes> {
     let (fields, tickets) = strtools::split_once(input.trim(), "\n\n");
 
     let fields: Vec<Field> = fields
@@ -108,7 +113,11 @@ fn parse_input(input: String) -> Result<(Vec<Field>, Ticket, Vec<Ticket>)> {
         .map(|n| Ticket::from_line(n.trim()))
         .collect::<Result<_, _>>()?;
 
-    Ok((fields, mine, nearby))
+    Ok(Notes {
+        fields,
+        mine,
+        nearby,
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -184,13 +193,13 @@ nearby tickets:
 
     #[test]
     fn part1_example() {
-        assert_eq!(71, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(71, part1(&parse(EXAMPLE).unwrap()).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        let (fields, _, tickets) = parse_input(EXAMPLE.to_string()).unwrap();
+        let notes = parse(EXAMPLE).unwrap();
 
-        assert_eq!(vec![1, 0, 2], solve_fields(&fields, &tickets).unwrap());
+        assert_eq!(vec![1, 0, 2], solve_fields(&notes.fields, &notes.nearby).unwrap());
     }
 }
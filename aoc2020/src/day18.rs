@@ -1,11 +1,11 @@
 use anyhow::Result;
 use itertools::Itertools;
 
-pub fn part1(input: String) -> Result<usize> {
+pub fn part1(input: &str) -> Result<usize> {
     Ok(input.lines().map(evaluate).sum())
 }
 
-pub fn part2(input: String) -> Result<usize> {
+pub fn part2(input: &str) -> Result<usize> {
     Ok(input
         .lines()
         .map(|l| {
@@ -163,10 +163,10 @@ impl<'a> Lexer<'a> {
             .char_indices()
             .find(|&(_, c)| !c.is_whitespace())?;
 
-        if c.is_digit(10) {
-            let end = (&self.expr[start..])
+        if c.is_ascii_digit() {
+            let end = self.expr[start..]
                 .char_indices()
-                .find(|&(_, c)| !c.is_digit(10))
+                .find(|&(_, c)| !c.is_ascii_digit())
                 .map(|(i, _)| i + start)
                 .unwrap_or(self.expr.len());
 
@@ -243,6 +243,6 @@ mod test {
 
     #[test]
     fn part2_examples() {
-        assert_eq!(231, part2(EXAMPLE1.to_string()).unwrap());
+        assert_eq!(231, part2(EXAMPLE1).unwrap());
     }
 }
@@ -5,8 +5,8 @@ use aoclib::strtools;
 use regex::Regex;
 use itertools::Itertools;
 
-pub fn part1(input: String) -> Result<usize> {
-    let (rules, messages) = strtools::split_once(input.as_str(), "\n\n");
+pub fn part1(input: &str) -> Result<usize> {
+    let (rules, messages) = strtools::split_once(input, "\n\n");
     let rule = build_regex(rules, "0");
     let rule = Regex::new(format!("^{}$", rule).as_str()).unwrap();
 
@@ -16,8 +16,8 @@ pub fn part1(input: String) -> Result<usize> {
     )
 }
 
-pub fn part2(input: String) -> Result<usize> {
-    let (rules, messages) = strtools::split_once(input.as_str(), "\n\n");
+pub fn part2(input: &str) -> Result<usize> {
+    let (rules, messages) = strtools::split_once(input, "\n\n");
     let rule42 = build_regex(rules, "42");
     let rule42 = Regex::new(format!("^{}", rule42).as_str()).unwrap();
 
@@ -99,7 +99,7 @@ aaaabbb"#;
 
     #[test]
     fn part1_example() {
-        assert_eq!(2, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(2, part1(EXAMPLE).unwrap());
     }
 
     const EXAMPLE2: &str = r#"42: 9 14 | 10 1
@@ -153,6 +153,6 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
 
     #[test]
     fn part2_example() {
-        assert_eq!(12, part2(EXAMPLE2.to_string()).unwrap());
+        assert_eq!(12, part2(EXAMPLE2).unwrap());
     }
 }
\ No newline at end of file
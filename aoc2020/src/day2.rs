@@ -1,7 +1,7 @@
 use std::convert::Infallible;
 use std::str::FromStr;
 
-pub fn part1(input: String) -> Result<usize, Infallible>
+pub fn part1(input: &str) -> Result<usize, Infallible>
 {
     let res = parse_input(input)
         .into_iter()
@@ -10,7 +10,7 @@ pub fn part1(input: String) -> Result<usize, Infallible>
     Ok(res)
 }
 
-pub fn part2(input: String) -> Result<usize, Infallible>
+pub fn part2(input: &str) -> Result<usize, Infallible>
 {
     let res = parse_input(input)
         .into_iter()
@@ -70,7 +70,7 @@ impl FromStr for PasswordPolicy {
     }
 }
 
-fn parse_input(input: String) -> Vec<(PasswordPolicy, String)>
+fn parse_input(input: &str) -> Vec<(PasswordPolicy, String)>
 {
     input
         .lines()
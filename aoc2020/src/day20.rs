@@ -1,14 +1,14 @@
 use anyhow::{anyhow, ensure, Result};
+use aoclib::grid::{Grid, Orientation};
 use aoclib::strtools;
 use itertools::Itertools;
 use std::{collections::HashMap, fmt::Display};
 
-pub fn part1(input: String) -> Result<u64> {
-    let tiles = parse_input(input)?;
-
+pub fn part1(tiles: &Tiles) -> Result<u64> {
     let corners: Vec<u64> = tiles
-        .into_iter()
-        .filter_map(|(num, t)| if t.is_corner() { Some(num) } else { None })
+        .0
+        .iter()
+        .filter_map(|(&num, t)| if t.is_corner() { Some(num) } else { None })
         .collect();
 
     assert_eq!(4, corners.len());
@@ -16,8 +16,8 @@ pub fn part1(input: String) -> Result<u64> {
     Ok(corners.into_iter().product())
 }
 
-pub fn part2(input: String) -> Result<u64> {
-    let mut tiles = parse_input(input)?;
+pub fn part2(tiles: &Tiles) -> Result<u64> {
+    let mut tiles = tiles.0.clone();
 
     let num_tiles = tiles.len();
     let bigside = {
@@ -38,9 +38,7 @@ pub fn part2(input: String) -> Result<u64> {
 
     let mut first = tiles.remove(&first_corner).unwrap();
 
-    while first.has_match(Position::Top) || first.has_match(Position::Left) {
-        first.rotate();
-    }
+    first.fiddle_until(|t| !t.has_match(Position::Top) && !t.has_match(Position::Left));
 
     let mut grid = vec![first];
 
@@ -67,7 +65,10 @@ pub fn part2(input: String) -> Result<u64> {
     Ok(big_tile.roughness())
 }
 
-fn parse_input(input: String) -> Result<HashMap<u64, PartialTile>> {
+/// Every tile, keyed by number, along with which of the other tiles each side matches
+pub struct Tiles(HashMap<u64, PartialTile>);
+
+pub fn parse(input: &str) -> Result<Tiles> {
     let mut tiles: HashMap<u64, PartialTile> = input
         .split("\n\n")
         .map(PartialTile::from_input)
@@ -80,7 +81,7 @@ fn parse_input(input: String) -> Result<HashMap<u64, PartialTile>> {
         tiles.get_mut(&k).unwrap().side_matches = matches;
     }
 
-    Ok(tiles)
+    Ok(Tiles(tiles))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -91,9 +92,29 @@ enum Position {
     Left,
 }
 
+impl Position {
+    /// Where this side ends up when its tile is turned to `orientation`
+    fn orient(self, orientation: Orientation) -> Self {
+        let mut pos = match (orientation.flipped, self) {
+            (true, Position::Left) => Position::Right,
+            (true, Position::Right) => Position::Left,
+            (_, pos) => pos,
+        };
+        for _ in 0..orientation.turns {
+            pos = match pos {
+                Position::Top => Position::Right,
+                Position::Right => Position::Bottom,
+                Position::Bottom => Position::Left,
+                Position::Left => Position::Top,
+            };
+        }
+        pos
+    }
+}
+
 struct Side {
     /// The characters on the side when traversed clockwise.
-    side: Vec<u8>,
+    side: Vec<char>,
 }
 
 impl Side {
@@ -110,41 +131,31 @@ impl Side {
 
 #[derive(Debug, Clone)]
 struct Tile {
-    side: usize,
-    tile: Vec<u8>,
+    grid: Grid<char>,
 }
 
 impl Display for Tile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in 0..self.side {
-            writeln!(f, "{}", unsafe {
-                std::str::from_utf8_unchecked(self.row(row).unwrap())
-            })?;
-        }
-        Ok(())
+        writeln!(f, "{}", self.grid)
     }
 }
 
 impl Tile {
     fn from_input(input: &str) -> Result<Self> {
-        let mut tile = Vec::new();
-        let side = input.trim().lines().count();
-
-        for line in input.trim().lines() {
-            ensure!(line.trim().bytes().count() == side, "tile must be square");
-            tile.extend(line.bytes());
-        }
+        let grid: Grid<char> = input.trim().parse()?;
+        ensure!(grid.rows() == grid.cols(), "tile must be square");
 
-        debug_assert!(tile.len() == side * side);
-
-        Ok(Self { side, tile })
+        Ok(Self { grid })
     }
 
     fn from_partial_tiles(side: usize, tiles: &[PartialTile]) -> Result<Self> {
-        let small_side = tiles
+        let inner: Vec<Grid<char>> = tiles.iter().map(|p| p.tile.grid.trim_border(1)).collect();
+        let small_side = inner
             .first()
-            .and_then(|p| p.tile.side.checked_sub(2))
+            .map(Grid::rows)
+            .filter(|&s| s > 0)
             .ok_or_else(|| anyhow!("failed to get small_side"))?;
+        ensure!(inner.len() == side * side, "expected {} tiles", side * side);
         let big_side = small_side * side;
 
         let mut tile = Vec::with_capacity(big_side * big_side);
@@ -152,52 +163,27 @@ impl Tile {
         for grid_row in 0..side {
             for small_row in 0..small_side {
                 for grid_col in 0..side {
-                    let row = tiles[grid_row * side + grid_col]
-                        .tile
-                        .row(small_row + 1)
-                        .map(|r| &r[1..(small_side + 1)])
-                        .ok_or_else(|| {
-                            anyhow!(
-                                "row {} not found for tile at ({}, {})",
-                                small_row,
-                                grid_row,
-                                grid_col
-                            )
-                        })?;
-                    tile.extend(row);
+                    tile.extend(inner[grid_row * side + grid_col].row(small_row));
                 }
             }
         }
 
-        debug_assert!(tile.len() == big_side * big_side);
-
         Ok(Self {
-            side: big_side,
-            tile,
+            grid: Grid::from_vec(tile, big_side),
         })
     }
 
-    fn get(&self, r: usize, c: usize) -> Option<u8> {
-        if r < self.side && c < self.side {
-            Some(self.tile[r * self.side + c])
-        } else {
-            None
-        }
+    fn side(&self) -> usize {
+        self.grid.rows()
     }
 
     fn get_side(&self, pos: Position) -> Side {
-        let last = self.side - 1;
-        let side: Vec<u8> = match pos {
-            Position::Top => (0..self.side).map(|c| self.get(0, c).unwrap()).collect(),
-            Position::Right => (0..self.side).map(|r| self.get(r, last).unwrap()).collect(),
-            Position::Bottom => (0..self.side)
-                .rev()
-                .map(|c| self.get(last, c).unwrap())
-                .collect(),
-            Position::Left => (0..self.side)
-                .rev()
-                .map(|r| self.get(r, 0).unwrap())
-                .collect(),
+        let last = self.side() - 1;
+        let side: Vec<char> = match pos {
+            Position::Top => self.grid.row(0).to_vec(),
+            Position::Right => self.grid.col(last).copied().collect(),
+            Position::Bottom => self.grid.row(last).iter().rev().copied().collect(),
+            Position::Left => self.grid.col(0).rev().copied().collect(),
         };
         Side { side }
     }
@@ -211,97 +197,53 @@ impl Tile {
         ]
     }
 
-    // Clockwise 90 degree rotation
-    fn rotate(&mut self) {
-        let mut new: Vec<u8> = Vec::with_capacity(self.tile.len());
-
-        for c in 0..self.side {
-            for r in (0..self.side).rev() {
-                new.push(self.tile[r * self.side + c]);
-            }
-        }
-
-        debug_assert!(new.len() == self.side * self.side);
-
-        self.tile = new;
-    }
-
-    // Flip across vertical axis
-    fn flip(&mut self) {
-        for chunk in self.tile.chunks_exact_mut(self.side) {
-            chunk.reverse();
-        }
-
-        debug_assert!(self.tile.len() == self.side * self.side);
-    }
-
-    fn row(&self, row: usize) -> Option<&[u8]> {
-        self.tile.chunks_exact(self.side).nth(row)
-    }
-
+    /// Reorient the tile until `pred` holds, returning false if it never does
     fn fiddle_until<Pred>(&mut self, mut pred: Pred) -> bool
     where
         Pred: FnMut(&Self) -> bool,
     {
-        for _ in 0..3 {
-            if pred(self) {
-                return true;
+        let found = self
+            .grid
+            .orientations()
+            .map(|grid| Tile { grid })
+            .find(|t| pred(t));
+        match found {
+            Some(tile) => {
+                *self = tile;
+                true
             }
-            self.rotate();
-        }
-        if pred(self) {
-            return true;
+            None => false,
         }
-        self.flip();
-        for _ in 0..3 {
-            if pred(self) {
-                return true;
-            }
-            self.rotate();
-        }
-        pred(self)
     }
 
     fn sea_monster_check(&self, row: usize, col: usize) -> bool {
-        SEA_MONSTER_POINTS.iter().all(|&(dr, dc)| {
-            let r = row + dr;
-            let c = col + dc;
-            self.tile[r * self.side + c] == b'#'
-        })
+        SEA_MONSTER_POINTS
+            .iter()
+            .all(|&(dr, dc)| self.grid.get((row + dr, col + dc)) == Some(&'#'))
+    }
+
+    fn sea_monsters(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.grid
+            .positions()
+            .filter(move |&(r, c)| self.sea_monster_check(r, c))
     }
 
     fn has_sea_monsters(&self) -> bool {
-        for r in 0..(self.side - 3) {
-            for c in 0..(self.side - 20) {
-                if self.sea_monster_check(r, c) {
-                    return true;
-                }
-            }
-        }
-        false
+        self.sea_monsters().next().is_some()
     }
 
     fn clear_sea_monster(&mut self) {
-        let mut points = Vec::new();
-        for r in 0..(self.side - 3) {
-            for c in 0..(self.side - 20) {
-                if self.sea_monster_check(r, c) {
-                    points.push((r, c))
-                }
-            }
-        }
+        let points: Vec<(usize, usize)> = self.sea_monsters().collect();
 
         for (r, c) in points {
             for (dr, dc) in &SEA_MONSTER_POINTS {
-                let row = r + dr;
-                let col = c + dc;
-                self.tile[row * self.side + col] = b'.';
+                self.grid[(r + dr, c + dc)] = '.';
             }
         }
     }
 
     fn roughness(&self) -> u64 {
-        self.tile.iter().filter(|&&x| x == b'#').count() as u64
+        self.grid.iter().filter(|&&x| x == '#').count() as u64
     }
 }
 
@@ -379,36 +321,21 @@ impl PartialTile {
         self.side_matches.len() == 2
     }
 
-    fn rotate(&mut self) {
-        self.tile.rotate();
-
-        for &mut (ref mut pos, _) in self.side_matches.iter_mut() {
-            let rotated = match pos {
-                Position::Top => Position::Right,
-                Position::Right => Position::Bottom,
-                Position::Bottom => Position::Left,
-                Position::Left => Position::Top,
-            };
-            *pos = rotated;
-        }
-    }
-
-    fn flip(&mut self) {
-        self.tile.flip();
-
-        for &mut (ref mut pos, _) in self.side_matches.iter_mut() {
-            let rotated = match pos {
-                Position::Top => Position::Top,
-                Position::Right => Position::Left,
-                Position::Bottom => Position::Bottom,
-                Position::Left => Position::Right,
-            };
-            *pos = rotated;
+    fn orient(&self, orientation: Orientation) -> Self {
+        Self {
+            side_matches: self
+                .side_matches
+                .iter()
+                .map(|&(pos, num)| (pos.orient(orientation), num))
+                .collect(),
+            tile: Tile {
+                grid: self.tile.grid.orient(orientation),
+            },
         }
     }
 
     fn has_match(&self, pos: Position) -> bool {
-        matches!(self.side_matches.iter().find(|(p, _)| *p == pos), Some(_))
+        self.side_matches.iter().any(|(p, _)| *p == pos)
     }
 
     fn get_match(&self, pos: Position) -> Option<u64> {
@@ -418,27 +345,21 @@ impl PartialTile {
             .map(|&(_, num)| num)
     }
 
+    /// Reorient the tile and its matches until `pred` holds, returning false if it never does
     fn fiddle_until<Pred>(&mut self, mut pred: Pred) -> bool
     where
         Pred: FnMut(&Self) -> bool,
     {
-        for _ in 0..3 {
-            if pred(self) {
-                return true;
+        let found = Orientation::all()
+            .map(|o| self.orient(o))
+            .find(|t| pred(t));
+        match found {
+            Some(tile) => {
+                *self = tile;
+                true
             }
-            self.rotate();
+            None => false,
         }
-        if pred(self) {
-            return true;
-        }
-        self.flip();
-        for _ in 0..3 {
-            if pred(self) {
-                return true;
-            }
-            self.rotate();
-        }
-        pred(self)
     }
 }
 
@@ -556,11 +477,29 @@ Tile 3079:
 
     #[test]
     fn part1_example() {
-        assert_eq!(20_899_048_083_289u64, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(20_899_048_083_289u64, part1(&parse(EXAMPLE).unwrap()).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(273u64, part2(EXAMPLE.to_string()).unwrap());
+        assert_eq!(273u64, part2(&parse(EXAMPLE).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn orient_moves_matches_with_their_sides() {
+        let tiles = parse(EXAMPLE).unwrap();
+        let tile = &tiles.0[&1951];
+        for o in Orientation::all() {
+            let oriented = tile.orient(o);
+            for &(pos, _) in tile.side_matches.iter() {
+                let before = tile.tile.get_side(pos);
+                let after = oriented.tile.get_side(pos.orient(o));
+                if o.flipped {
+                    assert!(after.matches(&before), "{:?} {:?}", o, pos);
+                } else {
+                    assert!(after.matches_flipped(&before), "{:?} {:?}", o, pos);
+                }
+            }
+        }
     }
 }
@@ -4,7 +4,7 @@ use anyhow::Result;
 use aoclib::strtools;
 use itertools::Itertools;
 
-pub fn part1(input: String) -> Result<usize> {
+pub fn part1(input: &str) -> Result<usize> {
     let recipes: Vec<Recipe<'_>> = input.lines()
         .map(Recipe::from_line)
         .collect();
@@ -37,7 +37,7 @@ pub fn part1(input: String) -> Result<usize> {
     )
 }
 
-pub fn part2(input: String) -> Result<String> {
+pub fn part2(input: &str) -> Result<String> {
     let recipes: Vec<Recipe<'_>> = input.lines()
         .map(Recipe::from_line)
         .collect();
@@ -120,7 +120,6 @@ mod test {
                 trh fvjkl sbzzf mxmxvkd (contains dairy)
                 sqjhc fvjkl (contains soy)
                 sqjhc mxmxvkd sbzzf (contains fish)"
-                .to_string()
             ).unwrap(),
         );
     }
@@ -4,8 +4,8 @@ use std::cmp::Ordering;
 use anyhow::Result;
 use aoclib::strtools;
 
-pub fn part1(input: String) -> Result<usize> {
-    let mut game = Combat::from_input(input.as_str())?;
+pub fn part1(input: &str) -> Result<usize> {
+    let mut game = Combat::from_input(input)?;
 
     while !game.is_over() {
         game.play_round();
@@ -20,8 +20,8 @@ pub fn part1(input: String) -> Result<usize> {
     )
 }
 
-pub fn part2(input: String) -> Result<usize> {
-    let game = Combat::from_input(input.as_str())?.recursive();
+pub fn part2(input: &str) -> Result<usize> {
+    let game = Combat::from_input(input)?.recursive();
 
     let (game, _) = game.play_to_end();
 
@@ -175,11 +175,11 @@ mod test {
 
     #[test]
     fn part1_example() {
-        assert_eq!(306, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(306, part1(EXAMPLE).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(291, part2(EXAMPLE.to_string()).unwrap());
+        assert_eq!(291, part2(EXAMPLE).unwrap());
     }
 }
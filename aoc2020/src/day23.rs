@@ -1,11 +1,34 @@
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Result};
+use aoclib::Params;
+
+/// Read the cup labels, which must be each of the digits from 1 up to the number of cups
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    let cups: Vec<usize> = input.trim()
+        .chars()
+        .map(|c| c.to_digit(10)
+            .map(|d| d as usize)
+            .ok_or_else(|| anyhow!("invalid cup label {:?}", c)))
+        .collect::<Result<_>>()?;
+
+    ensure!(!cups.is_empty(), "no cups in input");
+    // Each move picks up three cups and needs a destination other than the current cup
+    ensure!(cups.len() >= 5, "need at least 5 cups, found {}", cups.len());
+    let mut seen = vec![false; cups.len()+1];
+    for &cup in cups.iter() {
+        ensure!(cup >= 1 && cup <= cups.len(), "cup {} out of range 1-{}", cup, cups.len());
+        ensure!(!seen[cup], "cup {} appears more than once", cup);
+        seen[cup] = true;
+    }
+
+    Ok(cups)
+}
 
-pub fn part1(_: String) -> Result<String> {
-    part1_answer("974618352")
+pub fn part1(cups: &[usize], params: &Params) -> Result<String> {
+    part1_answer(cups, params.get("moves")?)
 }
 
-pub fn part2(_: String) -> Result<u64> {
-    part2_answer("974618352")
+pub fn part2(cups: &[usize], params: &Params) -> Result<u64> {
+    part2_answer(cups, params.get("moves")?)
 }
 
 struct CupList {
@@ -50,24 +73,20 @@ impl Extend<usize> for CupList {
     }
 }
 
-fn part1_cups(s: &str) -> CupList {
-    let mut cups = CupList::with_size(11);
+fn part1_cups(labels: &[usize]) -> CupList {
+    let mut cups = CupList::with_size(labels.len()+1);
 
-    cups.extend(
-        s.bytes().map(|c| c - b'0').map(|c| c.into())
-    );
+    cups.extend(labels.iter().copied());
 
     cups
 }
 
-fn part2_cups(s: &str) -> CupList {
+fn part2_cups(labels: &[usize]) -> CupList {
     let mut cups = CupList::with_size(1_000_001);
 
-    cups.extend(
-        s.bytes().map(|c| c - b'0').map(|c| c.into())
-    );
+    cups.extend(labels.iter().copied());
 
-    let next = s.bytes().count()+1;
+    let next = labels.len()+1;
     cups.extend(next..=1_000_000);
 
     cups
@@ -81,10 +100,10 @@ fn play_crab_cups(mut cups: CupList, iterations: usize) -> CupList {
     cups
 }
 
-fn part1_answer(input: &str) -> Result<String> {
+fn part1_answer(input: &[usize], moves: usize) -> Result<String> {
     let cups = play_crab_cups(
         part1_cups(input),
-        100,
+        moves,
     );
 
     let mut idx = cups.cups[1];
@@ -99,10 +118,10 @@ fn part1_answer(input: &str) -> Result<String> {
     Ok(s)
 }
 
-fn part2_answer(input: &str) -> Result<u64> {
+fn part2_answer(input: &[usize], moves: usize) -> Result<u64> {
     let cups = play_crab_cups(
         part2_cups(input),
-        10_000_000,
+        moves,
     );
 
     let a = cups.cups[1];
@@ -153,11 +172,27 @@ mod test {
 
     #[test]
     fn part1_example() {
-        assert_eq!("67384529", part1_answer("389125467").unwrap());
+        let cups = parse("389125467").unwrap();
+        assert_eq!("92658374", part1_answer(&cups, 10).unwrap());
+        assert_eq!("67384529", part1_answer(&cups, 100).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(149245887792, part2_answer("389125467").unwrap());
+        let cups = parse("389125467").unwrap();
+        assert_eq!(149245887792, part2_answer(&cups, 10_000_000).unwrap());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_cups() {
+        assert_eq!("invalid cup label 'x'", parse("38912x467").unwrap_err().to_string());
+        assert_eq!("cup 3 appears more than once", parse("3891254673").unwrap_err().to_string());
+        assert_eq!("cup 9 out of range 1-8", parse("38912546").unwrap_err().to_string());
+        assert_eq!("cup 0 out of range 1-9", parse("380125467").unwrap_err().to_string());
+        assert_eq!("no cups in input", parse("\n").unwrap_err().to_string());
+        assert_eq!("need at least 5 cups, found 2", parse("12").unwrap_err().to_string());
+        assert_eq!("need at least 5 cups, found 4", parse("1234").unwrap_err().to_string());
+        assert_eq!("need at least 5 cups, found 4", parse("4321").unwrap_err().to_string());
+        assert!(parse("21543").is_ok());
     }
 }
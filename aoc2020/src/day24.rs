@@ -1,26 +1,39 @@
-use std::collections::{HashSet, HashMap};
-
 use anyhow::Result;
+use aoclib::automaton::{Automaton, Rule, Sparse};
+use aoclib::geom::Point2;
+use aoclib::Params;
+
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(black_tiles(input).len())
+}
+
+pub fn part2(input: &str, params: &Params) -> Result<usize> {
+    let days: usize = params.get("days")?;
+    let mut tiles = Automaton::new(black_tiles(input), Rule::new(vec![2], 1..=2), neighbors);
 
-pub fn part1(input: String) -> Result<usize> {
-    let tiles = TileMap::from_input(input.as_str());
+    tiles.run(days);
 
-    Ok(tiles.count())
+    Ok(tiles.population())
 }
 
-pub fn part2(input: String) -> Result<usize> {
-    let mut tiles = TileMap::from_input(input.as_str());
+/// The tiles left black after flipping the tile at the end of each line
+fn black_tiles(input: &str) -> Sparse<Point2> {
+    let mut tiles = Sparse::new();
 
-    for _ in 0..100 {
-        tiles = tiles.next_day();
+    for line in input.trim().lines() {
+        let tile = DirList::new(line)
+            .map(|d| d.into_coords())
+            .fold(Point2::ORIGIN, |tile, d| tile + d);
+        if !tiles.insert(tile) {
+            tiles.remove(&tile);
+        }
     }
 
-    Ok(tiles.count())
+    tiles
 }
 
-struct TileMap(HashMap<(i32,i32), bool>);
-
-fn _neighbors(tile: (i32, i32)) -> Vec<(i32, i32)> {
+fn neighbors(tile: &Point2) -> impl Iterator<Item = Point2> {
+    let tile = *tile;
     vec![
         Direction::East,
         Direction::SouthEast,
@@ -30,55 +43,7 @@ fn _neighbors(tile: (i32, i32)) -> Vec<(i32, i32)> {
         Direction::NorthEast,
     ].into_iter()
         .map(|d| d.into_coords())
-        .map(|(x, y)| (x+tile.0, y+tile.1))
-        .collect()
-}
-
-impl TileMap {
-    fn from_input(input: &str) -> Self {
-        let mut tiles: HashMap<(i32, i32), bool> = HashMap::new();
-
-        for line in input.trim().lines() {
-            let tile = DirList::new(line)
-                .map(|d| d.into_coords())
-                .fold((0, 0), |(a, b), (x, y)| (a+x, b+y));
-            tiles.entry(tile)
-                .and_modify(|t| *t = !*t)
-                .or_insert(true);
-        }
-
-        Self(tiles)
-    }
-
-    fn count(&self) -> usize {
-        self.0.iter().filter(|(_, &b)| b).count()
-    }
-
-    fn next_day(self) -> Self {
-        let mut tiles: HashMap<(i32, i32), usize> = HashMap::new();
-
-        let black_tiles: HashSet<(i32, i32)> = self.0.into_iter()
-            .filter_map(|(t, b)| if b { Some(t) } else { None })
-            .collect();
-
-        for tile in black_tiles.iter().cloned() {
-            for neighbor in _neighbors(tile) {
-                tiles.entry(neighbor)
-                    .and_modify(|t| *t += 1)
-                    .or_insert(1);
-            }
-        }
-
-        let mut new_tiles: HashMap<(i32, i32), bool> = HashMap::new();
-
-        for (tile, count) in tiles {
-            if count == 2 || (count == 1 && black_tiles.contains(&tile)) {
-                new_tiles.insert(tile, true);
-            }
-        }
-
-        Self(new_tiles)
-    }
+        .map(move |d| tile + d)
 }
 
 enum Direction {
@@ -91,15 +56,15 @@ enum Direction {
 }
 
 impl Direction {
-    fn into_coords(self) -> (i32, i32) {
-        match self {
-            Direction::East => (1, 0),
-            Direction::West => (-1, 0),
-            Direction::NorthEast => (0, 1),
-            Direction::NorthWest => (-1, 1),
-            Direction::SouthEast => (1, -1),
-            Direction::SouthWest => (0, -1),
-        }
+    fn into_coords(self) -> Point2 {
+        Point2::new(match self {
+            Direction::East => [1, 0],
+            Direction::West => [-1, 0],
+            Direction::NorthEast => [0, 1],
+            Direction::NorthWest => [-1, 1],
+            Direction::SouthEast => [1, -1],
+            Direction::SouthWest => [0, -1],
+        })
     }
 }
 
@@ -166,11 +131,12 @@ wseweeenwnesenwwwswnew";
 
     #[test]
     fn part1_example() {
-        assert_eq!(10, part1(EXAMPLE.to_string()).unwrap());
+        assert_eq!(10, part1(EXAMPLE).unwrap());
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(2208, part2(EXAMPLE.to_string()).unwrap());
+        assert_eq!(15, part2(EXAMPLE, &Params::new().set("days", 1)).unwrap());
+        assert_eq!(2208, part2(EXAMPLE, &Params::new().set("days", 100)).unwrap());
     }
 }
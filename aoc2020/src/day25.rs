@@ -1,16 +1,17 @@
 use anyhow::{anyhow, Result};
+use aoclib::Cancel;
 use num_bigint::BigUint;
 
 const MODULUS: u32 = 20201227;
 const BASE: u8 = 7;
 
-pub fn part1(input: String) -> Result<BigUint> {
+pub fn part1(input: &str, cancel: &Cancel) -> Result<BigUint> {
     let public_keys: Vec<BigUint> = input
         .lines()
         .map(|l| l.trim().parse::<BigUint>())
         .collect::<Result<_, _>>()?;
 
-    let solved_key = find_private_key(&public_keys).ok_or(anyhow!("cannot find private key"))?;
+    let solved_key = find_private_key(&public_keys, cancel)?;
 
     Ok(public_keys
         .into_iter()
@@ -25,17 +26,18 @@ pub fn part1(input: String) -> Result<BigUint> {
         .unwrap())
 }
 
-pub fn part2(_input: String) -> Result<BigUint> {
+pub fn part2(_input: &str) -> Result<BigUint> {
     Ok(0u8.into())
 }
 
-fn find_private_key(public_keys: &[BigUint]) -> Option<CryptoKey> {
+fn find_private_key(public_keys: &[BigUint], cancel: &Cancel) -> Result<CryptoKey> {
     let mut exponent: BigUint = 1u8.into();
     let mut public_key: BigUint = BASE.into();
 
     while exponent < MODULUS.into() {
+        cancel.check()?;
         if public_keys.contains(&public_key) {
-            return Some(CryptoKey {
+            return Ok(CryptoKey {
                 public_key,
                 private_key: exponent,
             });
@@ -44,7 +46,7 @@ fn find_private_key(public_keys: &[BigUint]) -> Option<CryptoKey> {
         exponent += 1u8;
         public_key = (public_key * BASE) % MODULUS;
     }
-    None
+    Err(anyhow!("cannot find private key"))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,7 +71,7 @@ mod test {
     fn part1_example() {
         assert_eq!(
             BigUint::from(14897079u32),
-            part1(EXAMPLE.to_string()).unwrap()
+            part1(EXAMPLE, &Cancel::new()).unwrap()
         );
     }
 }
@@ -1,9 +1,9 @@
 use std::convert::Infallible;
 
-struct Map(String);
+struct Map<'a>(&'a str);
 
-impl Map {
-    fn new(s: String) -> Self
+impl<'a> Map<'a> {
+    fn new(s: &'a str) -> Self
     {
         Self(s)
     }
@@ -23,13 +23,13 @@ impl Map {
     }
 }
 
-pub fn part1(input: String) -> Result<usize, Infallible>
+pub fn part1(input: &str) -> Result<usize, Infallible>
 {
     Ok(Map::new(input).count_trees(3, 1))
 }
 
 
-pub fn part2(input: String) -> Result<usize, Infallible>
+pub fn part2(input: &str) -> Result<usize, Infallible>
 {
     let slopes = vec!(
         (1, 1),
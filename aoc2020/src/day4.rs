@@ -33,7 +33,7 @@ impl Passport {
 
     fn validate_height(&self) -> bool {
         self.0
-            .get(&"hgt".to_string())
+            .get("hgt")
             .map(|val| {
                 if let Some(val) = val.strip_suffix("in") {
                     return is_num_between(val, 59, 76);
@@ -48,12 +48,12 @@ impl Passport {
 
     fn validate_hair_color(&self) -> bool {
         self.0
-            .get(&"hcl".to_string())
+            .get("hcl")
             .map(|val| {
                 val.strip_prefix('#')
                     .map(|hex| {
                         hex.chars()
-                            .all(|c| ('0'..='9').contains(&c) || ('a'..='f').contains(&c))
+                            .all(|c: char| c.is_ascii_digit() || ('a'..='f').contains(&c))
                     })
                     .unwrap_or(false)
             })
@@ -62,7 +62,7 @@ impl Passport {
 
     fn validate_eye_color(&self) -> bool {
         self.0
-            .get(&"ecl".to_string())
+            .get("ecl")
             .map(|val| {
                 matches!(
                     val.as_str(),
@@ -74,7 +74,7 @@ impl Passport {
 
     fn validate_id(&self) -> bool {
         self.0
-            .get(&"pid".to_string())
+            .get("pid")
             .map(|val| val.len() == 9 && val.chars().all(|c| c.is_ascii_digit()))
             .unwrap_or(false)
     }
@@ -108,7 +108,7 @@ impl FromStr for Passport {
     }
 }
 
-pub fn part1(input: String) -> Result<usize, Infallible> {
+pub fn part1(input: &str) -> Result<usize, Infallible> {
     let entries = input.split("\n\n");
 
     let num = entries
@@ -118,7 +118,7 @@ pub fn part1(input: String) -> Result<usize, Infallible> {
     Ok(num)
 }
 
-pub fn part2(input: String) -> Result<usize, Infallible> {
+pub fn part2(input: &str) -> Result<usize, Infallible> {
     let entries = input.split("\n\n");
 
     let num = entries
@@ -148,7 +148,7 @@ iyr:2011 ecl:brn hgt:59in";
 
     #[test]
     fn part1_example() {
-        assert_eq!(Ok(2), part1(EXAMPLE.to_string()));
+        assert_eq!(Ok(2), part1(EXAMPLE));
     }
 
     const INVALID: &str = "eyr:1972 cid:100
@@ -167,7 +167,7 @@ pid:3556412378 byr:2007";
 
     #[test]
     fn part2_invalid() {
-        assert_eq!(Ok(0), part2(INVALID.to_string()));
+        assert_eq!(Ok(0), part2(INVALID));
     }
 
     const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
@@ -185,6 +185,6 @@ iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
 
     #[test]
     fn part2_valid() {
-        assert_eq!(Ok(4), part2(VALID.to_string()));
+        assert_eq!(Ok(4), part2(VALID));
     }
 }
@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use super::error::AocError;
+
+#[derive(Debug, PartialEq)]
+struct BoardingPass {
+    row: u32,
+    col: u32,
+}
+
+impl BoardingPass {
+    fn seat_id(&self) -> u32 {
+        self.row * 8 + self.col
+    }
+}
+
+/// Decode a binary partition where `lower` keeps the front half and `upper` the back half
+fn decode(s: &str, lower: u8, upper: u8) -> Result<u32, AocError> {
+    let mut val = 0;
+    for c in s.bytes() {
+        val <<= 1;
+        if c == upper {
+            val |= 1;
+        } else if c != lower {
+            return Err(AocError::ParseError(format!("invalid character {:?} in boarding pass", c as char)))
+        }
+    }
+    Ok(val)
+}
+
+impl FromStr for BoardingPass {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 10 || !s.is_ascii() {
+            return Err(AocError::ParseError(format!("invalid boarding pass {:?}", s)))
+        }
+        let (row, col) = s.split_at(7);
+
+        Ok(BoardingPass {
+            row: decode(row, b'F', b'B')?,
+            col: decode(col, b'L', b'R')?,
+        })
+    }
+}
+
+fn seat_ids(input: &str) -> Result<Vec<u32>, AocError> {
+    input.lines()
+        .map(|l| l.trim().parse::<BoardingPass>().map(|bp| bp.seat_id()))
+        .collect()
+}
+
+pub fn part1(input: &str) -> Result<u32, AocError> {
+    seat_ids(input)?
+        .into_iter()
+        .max()
+        .ok_or_else(|| AocError::ParseError("no boarding passes".into()))
+}
+
+pub fn part2(input: &str) -> Result<u32, AocError> {
+    let mut ids = seat_ids(input)?;
+    ids.sort_unstable();
+
+    ids.windows(2)
+        .find(|w| w[1] == w[0] + 2)
+        .map(|w| w[0] + 1)
+        .ok_or_else(|| AocError::ParseError("no missing seat between two occupied seats".into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_examples() {
+        assert_eq!(Ok(BoardingPass { row: 44, col: 5 }), "FBFBBFFRLR".parse());
+        assert_eq!(Ok(BoardingPass { row: 70, col: 7 }), "BFFFBBFRRR".parse());
+        assert_eq!(Ok(BoardingPass { row: 14, col: 7 }), "FFFBBBFRRR".parse());
+        assert_eq!(Ok(BoardingPass { row: 102, col: 4 }), "BBFFBBFRLL".parse());
+        assert!("FBFBBFFRLX".parse::<BoardingPass>().is_err());
+        assert!("FBFBBFF".parse::<BoardingPass>().is_err());
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Ok(820), part1("FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n"));
+    }
+
+    #[test]
+    fn part2_example() {
+        // Seat 357 (FBFBBFFRLR) is missing
+        assert_eq!(Ok(357), part2("FBFBBFFRLL\nFBFBBFFRRL\n"));
+    }
+
+    #[test]
+    fn part2_no_gap() {
+        // Seats 356, 357 and 358 are all taken
+        assert_eq!(
+            Err(AocError::ParseError("no missing seat between two occupied seats".into())),
+            part2("FBFBBFFRLL\nFBFBBFFRLR\nFBFBBFFRRL\n")
+        );
+    }
+}
@@ -30,7 +30,7 @@ impl FromStr for CustomsForm {
             let mut person: [bool; 26] = [false; 26];
 
             for letter in line.bytes() {
-                if !(b'a'..=b'z').contains(&letter) {
+                if !letter.is_ascii_lowercase() {
                     return Err(AocError::ParseError("invalid character in customs form".into()))
                 }
                 person[(letter-b'a') as usize] = true;
@@ -48,7 +48,7 @@ impl FromStr for CustomsForm {
     }
 }
 
-pub fn part1(input: String) -> Result<usize, AocError> {
+pub fn part1(input: &str) -> Result<usize, AocError> {
     let mut total = 0;
     for entry in input.split("\n\n") {
         let cf = entry.parse::<CustomsForm>()?;
@@ -57,7 +57,7 @@ pub fn part1(input: String) -> Result<usize, AocError> {
     Ok(total)
 }
 
-pub fn part2(input: String) -> Result<usize, AocError> {
+pub fn part2(input: &str) -> Result<usize, AocError> {
     let mut total = 0;
     for entry in input.split("\n\n") {
         let cf = entry.parse::<CustomsForm>()?;
@@ -88,11 +88,11 @@ b";
 
     #[test]
     fn part1_example() {
-        assert_eq!(Ok(11), part1(EXAMPLE.to_string()));
+        assert_eq!(Ok(11), part1(EXAMPLE));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(Ok(6), part2(EXAMPLE.to_string()));
+        assert_eq!(Ok(6), part2(EXAMPLE));
     }
 }
\ No newline at end of file
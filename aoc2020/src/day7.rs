@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::iter::repeat;
 
 use super::error::AocError;
 
@@ -49,7 +48,7 @@ impl BagGraph {
 
             for (idx, b) in self.bags.iter().enumerate() {
                 for inner in b.must_contain.iter() {
-                    let contains = cur.iter().any(|c| *c == inner.1);
+                    let contains = cur.contains(&inner.1);
                     if contains {
                         next.push(BagIdx(idx))
                     }
@@ -70,7 +69,7 @@ impl BagGraph {
     }
 
     fn calc_cost(&self, bag: BagIdx) -> u32 {
-        let mut costs: Vec<Option<u32>> = repeat(None).take(self.bags.len()).collect();
+        let mut costs: Vec<Option<u32>> = std::iter::repeat_n(None, self.bags.len()).collect();
 
         for (idx, b) in self.bags.iter().enumerate() {
             if b.must_contain.is_empty() {
@@ -97,7 +96,7 @@ impl BagGraph {
     }
 }
 
-fn parse_input(input: String) -> Result<BagGraph, AocError> {
+fn parse_input(input: &str) -> Result<BagGraph, AocError> {
     let mut bags = BagGraph::new();
     let mut rules = Vec::new();
     let mut index: HashMap<String, BagIdx> = HashMap::new();
@@ -144,14 +143,14 @@ fn parse_input(input: String) -> Result<BagGraph, AocError> {
     Ok(bags)
 }
 
-pub fn part1(input: String) -> Result<usize, AocError> {
+pub fn part1(input: &str) -> Result<usize, AocError> {
     let bags = parse_input(input)?;
     Ok(bags
         .can_contain(bags.find_bag("shiny gold".to_string()).unwrap())
         .len())
 }
 
-pub fn part2(input: String) -> Result<u32, AocError> {
+pub fn part2(input: &str) -> Result<u32, AocError> {
     let bags = parse_input(input)?;
     Ok(bags.calc_cost(bags.find_bag("shiny gold".to_string()).unwrap()))
 }
@@ -172,12 +171,12 @@ dotted black bags contain no other bags.";
 
     #[test]
     fn part1_example() {
-        assert_eq!(Ok(4), part1(EXAMPLE.to_string()));
+        assert_eq!(Ok(4), part1(EXAMPLE));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(Ok(32), part2(EXAMPLE.to_string()));
+        assert_eq!(Ok(32), part2(EXAMPLE));
     }
 
     const EXAMPLE2: &str = "shiny gold bags contain 2 dark red bags.
@@ -190,6 +189,6 @@ dark violet bags contain no other bags.";
 
     #[test]
     fn part2_example2() {
-        assert_eq!(Ok(126), part2(EXAMPLE2.to_string()));
+        assert_eq!(Ok(126), part2(EXAMPLE2));
     }
 }
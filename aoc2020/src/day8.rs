@@ -1,16 +1,15 @@
-use std::iter::repeat;
 use std::str::FromStr;
 
 use super::error::AocError;
 
-pub fn part1(input: String) -> Result<i32, AocError> {
+pub fn part1(input: &str) -> Result<i32, AocError> {
     if let RunResult::InfiniteLoop(acc) = input.parse::<Program>()?.run() {
         return Ok(acc);
     }
     Err(AocError::Unknown)
 }
 
-pub fn part2(input: String) -> Result<i32, AocError> {
+pub fn part2(input: &str) -> Result<i32, AocError> {
     let mut prog = input.parse::<Program>()?;
 
     for i in 0..prog.0.len() {
@@ -78,7 +77,7 @@ enum RunResult {
 
 impl Program {
     fn run(&self) -> RunResult {
-        let mut visited: Vec<bool> = repeat(false).take(self.0.len()).collect();
+        let mut visited: Vec<bool> = std::iter::repeat_n(false, self.0.len()).collect();
         let mut eip = 0;
         let mut accumulator = 0;
         loop {
@@ -124,11 +123,11 @@ acc +6";
 
     #[test]
     fn part1_example() {
-        assert_eq!(Ok(5), part1(EXAMPLE.to_string()));
+        assert_eq!(Ok(5), part1(EXAMPLE));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(Ok(8), part2(EXAMPLE.to_string()));
+        assert_eq!(Ok(8), part2(EXAMPLE));
     }
 }
@@ -1,26 +1,30 @@
 use std::collections::{HashMap, VecDeque};
 
 use anyhow::{anyhow, ensure, Result};
+use aoclib::Params;
 use itertools::Itertools;
 use itertools::MinMaxResult::MinMax;
 
-pub fn part1(input: String) -> Result<u64> {
-    part1_with_preamble_len(25, input)
+pub fn parse(input: &str) -> Result<Vec<u64>> {
+    Ok(input
+        .lines()
+        .map(|l| l.parse())
+        .collect::<Result<Vec<u64>, _>>()?)
 }
 
-pub fn part2(input: String) -> Result<u64> {
-    part2_with_preamble_len(25, input)
+pub fn part1(nums: &[u64], params: &Params) -> Result<u64> {
+    part1_with_preamble_len(params.get("preamble")?, nums)
 }
 
-fn part1_with_preamble_len(preamble_len: usize, input: String) -> Result<u64> {
-    let nums = input
-        .lines()
-        .map(|l| l.parse())
-        .collect::<Result<Vec<u64>, _>>()?;
+pub fn part2(nums: &[u64], params: &Params) -> Result<u64> {
+    part2_with_preamble_len(params.get("preamble")?, nums)
+}
+
+fn part1_with_preamble_len(preamble_len: usize, nums: &[u64]) -> Result<u64> {
     ensure!(nums.len() >= preamble_len, "input too short");
 
     let mut xs = XmasState::new(&nums[..preamble_len]);
-    for num in nums.into_iter().skip(preamble_len) {
+    for &num in nums.iter().skip(preamble_len) {
         if !xs.is_valid(num) {
             return Ok(num);
         }
@@ -29,13 +33,9 @@ fn part1_with_preamble_len(preamble_len: usize, input: String) -> Result<u64> {
     Err(anyhow!("input is valid"))
 }
 
-pub fn part2_with_preamble_len(preamble_len: usize, input: String) -> Result<u64> {
+fn part2_with_preamble_len(preamble_len: usize, nums: &[u64]) -> Result<u64> {
     use std::cmp::Ordering;
-    let target = part1_with_preamble_len(preamble_len, input.clone())?;
-    let nums = input
-        .lines()
-        .map(|l| l.parse())
-        .collect::<Result<Vec<u64>, _>>()?;
+    let target = part1_with_preamble_len(preamble_len, nums)?;
 
     let mut start = 0;
     let mut end = 0;
@@ -141,16 +141,20 @@ mod test {
 309
 576";
 
+    fn params() -> Params {
+        Params::new().set("preamble", 5)
+    }
+
     #[test]
     fn part1_example() {
         assert_eq!(
             127,
-            part1_with_preamble_len(5, EXAMPLE.to_string()).unwrap()
+            part1(&parse(EXAMPLE).unwrap(), &params()).unwrap()
         );
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(62, part2_with_preamble_len(5, EXAMPLE.to_string()).unwrap());
+        assert_eq!(62, part2(&parse(EXAMPLE).unwrap(), &params()).unwrap());
     }
 }
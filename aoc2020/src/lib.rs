@@ -0,0 +1,119 @@
+use aoclib::{Day, Year};
+
+pub(crate) mod parse;
+pub(crate) mod error;
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+pub fn year() -> Year {
+    Year::new(2020)
+        .day(Day::new(1)
+            .part_str(1, day1::part1)
+            .part_str(2, day1::part2))
+        .day(Day::new(2)
+            .part_str(1, day2::part1)
+            .part_str(2, day2::part2))
+        .day(Day::new(3)
+            .part_str(1, day3::part1)
+            .part_str(2, day3::part2))
+        .day(Day::new(4)
+            .part_str(1, day4::part1)
+            .part_str(2, day4::part2))
+        .day(Day::new(5)
+            .part_str(1, day5::part1)
+            .part_str(2, day5::part2))
+        .day(Day::new(6)
+            .part_str(1, day6::part1)
+            .part_str(2, day6::part2))
+        .day(Day::new(7)
+            .part_str(1, day7::part1)
+            .part_str(2, day7::part2))
+        .day(Day::new(8)
+            .part_str(1, day8::part1)
+            .part_str(2, day8::part2))
+        .day(Day::new(9)
+            .parse(day9::parse)
+            .part_with_params(1, day9::part1)
+            .param(1, "preamble", 25)
+            .part_with_params(2, day9::part2)
+            .param(2, "preamble", 25))
+        .day(Day::new(10)
+            .part_str(1, day10::part1)
+            .part_str(2, day10::part2))
+        .day(Day::new(11)
+            .part_str(1, day11::part1)
+            .part_str(2, day11::part2))
+        .day(Day::new(12)
+            .part_str(1, day12::part1)
+            .part_str(2, day12::part2))
+        .day(Day::new(13)
+            .part_str(1, day13::part1)
+            .part_str(2, day13::part2))
+        .day(Day::new(14)
+            .part_str(1, day14::part1)
+            .part_str(2, day14::part2))
+        .day(Day::new(15)
+            .part_with_params(1, day15::part1)
+            .param(1, "turns", 2020)
+            .part_with_params(2, day15::part2)
+            .param(2, "turns", 30_000_000))
+        .day(Day::new(16)
+            .parse(day16::parse)
+            .part(1, day16::part1)
+            .part(2, day16::part2))
+        .day(Day::new(17)
+            .part_str(1, day17::part1)
+            .part_str(2, day17::part2))
+        .day(Day::new(18)
+            .part_str(1, day18::part1)
+            .part_str(2, day18::part2))
+        .day(Day::new(19)
+            .part_str(1, day19::part1)
+            .part_str(2, day19::part2))
+        .day(Day::new(20)
+            .parse(day20::parse)
+            .part(1, day20::part1)
+            .part(2, day20::part2))
+        .day(Day::new(21)
+            .part_str(1, day21::part1)
+            .part_str(2, day21::part2))
+        .day(Day::new(22)
+            .part_str(1, day22::part1)
+            .part_str(2, day22::part2))
+        .day(Day::new(23)
+            .parse(day23::parse)
+            .part_with_params(1, day23::part1)
+            .param(1, "moves", 100)
+            .part_with_params(2, day23::part2)
+            .param(2, "moves", 10_000_000))
+        .day(Day::new(24)
+            .part_str(1, day24::part1)
+            .part_with_params(2, day24::part2)
+            .param(2, "days", 100))
+        .day(Day::new(25)
+            .part_cancellable(1, day25::part1)
+            .part_str(2, day25::part2))
+}
@@ -1,6 +1,6 @@
 // Useful input parsers
 
-pub fn to_nums(input: String) -> Vec<u32>
+pub fn to_nums(input: &str) -> Vec<u32>
 {
     input
         .lines()
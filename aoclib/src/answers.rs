@@ -0,0 +1,309 @@
+//! Known-correct answers used to verify solvers
+//!
+//! Answers for a year are stored as TOML in `<dir>/<year>.toml`, with a table per day holding the
+//! answer to each part and optionally the SHA-256 of the input they were found with:
+//!
+//! ```toml
+//! [1]
+//! 1 = 514579
+//! 2 = "241861950"
+//! sha256 = "8a0c...e1f4"
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+
+use super::day::PartResult;
+use super::year::YearResult;
+
+/// Outcome of checking a part's answer against the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the recorded answer
+    Pass,
+    /// The answer differs from the recorded answer, or the part failed to produce one
+    Fail { expected: String },
+    /// There is no recorded answer for this part
+    New,
+    /// The day's input isn't the one the recorded answers were found with, which has the checksum
+    /// held
+    InputMismatch { expected: String },
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::New => write!(f, "NEW"),
+            Verdict::InputMismatch { .. } => write!(f, "INPUT MISMATCH"),
+        }
+    }
+}
+
+/// Key of the input checksum within a day's table
+const CHECKSUM_KEY: &str = "sha256";
+
+/// Recorded answers for a single year, keyed by day and part
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), String>,
+    /// Checksum of the input each day's answers were found with, where recorded
+    checksums: HashMap<u8, String>,
+}
+
+impl Answers {
+    /// Location of the answers for `year` within an answers directory
+    pub fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{}.toml", year))
+    }
+
+    /// Load the answers for `year`. A missing file means no answers are known yet.
+    pub fn load(dir: &Path, year: u16) -> Result<Self, anyhow::Error> {
+        let path = Answers::path(dir, year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Answers::parse(&s).with_context(|| format!("invalid {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, anyhow::Error> {
+        let mut answers = Answers::default();
+        let days = match s.parse::<toml::Value>()? {
+            toml::Value::Table(days) => days,
+            _ => bail!("expected a table of days"),
+        };
+
+        for (day, parts) in days {
+            let day: u8 = day
+                .parse()
+                .with_context(|| format!("invalid day {:?}", day))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| anyhow!("day {} must be a table of parts", day))?;
+            for (part, answer) in parts {
+                if part == CHECKSUM_KEY {
+                    let sum = answer
+                        .as_str()
+                        .ok_or_else(|| anyhow!("day {}: {} must be a string", day, CHECKSUM_KEY))?;
+                    answers.checksums.insert(day, sum.to_string());
+                    continue;
+                }
+                let part: u8 = part
+                    .parse()
+                    .with_context(|| format!("invalid part {:?} on day {}", part, day))?;
+                let answer = match answer {
+                    toml::Value::String(s) => s.clone(),
+                    toml::Value::Integer(i) => i.to_string(),
+                    _ => bail!(
+                        "day {} part {}: answer must be a string or integer",
+                        day,
+                        part
+                    ),
+                };
+                answers.answers.insert((day, part), answer);
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Record `answer` as the answer to a part in the answers for `year`, keeping any others, along
+    /// with the checksum of the input it was found with. That replaces the day's recorded checksum,
+    /// so check it matches first (as [`submit`] does).
+    ///
+    /// [`submit`]: crate::submit::submit
+    ///
+    /// The file is rewritten, so comments in it are lost.
+    pub fn record(
+        dir: &Path,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        checksum: &str,
+    ) -> Result<(), anyhow::Error> {
+        let path = Answers::path(dir, year);
+        let mut days = match fs::read_to_string(&path) {
+            Ok(s) => s
+                .parse::<toml::Value>()
+                .with_context(|| format!("invalid {}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => toml::Value::Table(Default::default()),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+
+        let parts = days
+            .as_table_mut()
+            .and_then(|days| {
+                days.entry(day.to_string())
+                    .or_insert_with(|| toml::Value::Table(Default::default()))
+                    .as_table_mut()
+            })
+            .ok_or_else(|| anyhow!("invalid {}: day {} is not a table", path.display(), day))?;
+        parts.insert(part.to_string(), toml::Value::String(answer.to_string()));
+        parts.insert(
+            CHECKSUM_KEY.to_string(),
+            toml::Value::String(checksum.to_string()),
+        );
+
+        let contents = toml::to_string(&days)?;
+        fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&path, contents))
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Checksum of the input the answers for `day` were found with, if it was recorded
+    pub fn checksum(&self, day: u8) -> Option<&str> {
+        self.checksums.get(&day).map(String::as_str)
+    }
+
+    /// Check an answer against the recorded one.
+    ///
+    /// Parts that failed with an error have no verdict unless an answer was recorded for them.
+    pub fn check(&self, day: u8, part: &PartResult) -> Option<Verdict> {
+        match (self.get(day, part.part), &part.answer) {
+            (Some(expected), Ok(answer)) if expected == answer => Some(Verdict::Pass),
+            (Some(expected), _) => Some(Verdict::Fail {
+                expected: expected.to_string(),
+            }),
+            (None, Ok(_)) => Some(Verdict::New),
+            (None, Err(_)) => None,
+        }
+    }
+
+    /// Record a verdict on every solved part of `result`.
+    ///
+    /// Every part of a day solved with a different input than its answers were recorded for gets
+    /// [`Verdict::InputMismatch`], since its answers can't be compared.
+    pub fn verify(&self, result: &mut YearResult) {
+        for d in result.days.iter_mut().flatten() {
+            let day = d.day;
+            let mismatch = self.checksum(day).filter(|&sum| sum != d.checksum);
+            for part in d.parts.iter_mut() {
+                part.verdict = match mismatch {
+                    Some(expected) => Some(Verdict::InputMismatch {
+                        expected: expected.to_string(),
+                    }),
+                    None => self.check(day, part),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn part(part: u8, answer: Result<&str, &str>) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map(String::from).map_err(|e| anyhow!("{}", e)),
+            duration: Duration::default(),
+            bench: None,
+            verdict: None,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("[1]\n1 = 514579\n2 = \"abc\"\n\n[25]\n1 = \"x\"\n").unwrap();
+        assert_eq!(Some("514579"), answers.get(1, 1));
+        assert_eq!(Some("abc"), answers.get(1, 2));
+        assert_eq!(Some("x"), answers.get(25, 1));
+        assert_eq!(None, answers.get(25, 2));
+
+        assert!(Answers::parse("[day1]\n1 = 1\n").is_err());
+        assert!(Answers::parse("[1]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[1]\n1 = 1.5\n").is_err());
+        assert!(Answers::parse("1 = 2\n").is_err());
+        assert!(Answers::parse("[1]\nsha256 = 1\n").is_err());
+
+        let answers = Answers::parse("[1]\n1 = 42\nsha256 = \"abc\"\n").unwrap();
+        assert_eq!(Some("42"), answers.get(1, 1));
+        assert_eq!(Some("abc"), answers.checksum(1));
+        assert_eq!(None, answers.checksum(2));
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[1]\n1 = 42\n2 = 7\n").unwrap();
+        assert_eq!(Some(Verdict::Pass), answers.check(1, &part(1, Ok("42"))));
+        assert_eq!(
+            Some(Verdict::Fail {
+                expected: "7".to_string()
+            }),
+            answers.check(1, &part(2, Ok("8")))
+        );
+        assert_eq!(
+            Some(Verdict::Fail {
+                expected: "7".to_string()
+            }),
+            answers.check(1, &part(2, Err("oops")))
+        );
+        assert_eq!(Some(Verdict::New), answers.check(2, &part(1, Ok("1"))));
+        assert_eq!(None, answers.check(2, &part(1, Err("oops"))));
+    }
+
+    #[test]
+    fn test_verify_input_mismatch() {
+        use crate::day::DayResult;
+
+        let answers = Answers::parse("[1]\n1 = 42\nsha256 = \"abc\"\n[2]\n1 = 7\n").unwrap();
+        let day = |day, checksum: &str| {
+            Ok(DayResult {
+                day,
+                checksum: checksum.to_string(),
+                parse: None,
+                parts: vec![part(1, Ok("42"))],
+            })
+        };
+        let mut result = YearResult {
+            year: 2020,
+            days: vec![day(1, "abc"), day(1, "def"), day(2, "def")],
+        };
+        answers.verify(&mut result);
+
+        let verdicts: Vec<_> = result
+            .days
+            .iter()
+            .flatten()
+            .map(|d| d.parts[0].verdict.clone().unwrap())
+            .collect();
+        assert_eq!(
+            vec![
+                Verdict::Pass,
+                Verdict::InputMismatch {
+                    expected: "abc".to_string()
+                },
+                Verdict::Fail {
+                    expected: "7".to_string()
+                },
+            ],
+            verdicts
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let dir = std::env::temp_dir().join(format!("aoclib-answers-{}", std::process::id()));
+        Answers::record(&dir, 2020, 3, 1, "12", "abc").unwrap();
+        Answers::record(&dir, 2020, 3, 2, "34", "abc").unwrap();
+        let answers = Answers::load(&dir, 2020).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Some("12"), answers.get(3, 1));
+        assert_eq!(Some("34"), answers.get(3, 2));
+        assert_eq!(Some("abc"), answers.checksum(3));
+    }
+}
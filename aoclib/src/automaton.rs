@@ -0,0 +1,411 @@
+//! Life-like cellular automata
+//!
+//! Each generation, a cell comes alive or stays alive depending on how many of its neighbours are
+//! alive, as decided by a [`Rule`]. What counts as a neighbour is up to the caller, and the cells
+//! can be kept in either of two backends:
+//!
+//! - [`Sparse`]: the set of live cells on an unbounded space of any coordinate type
+//! - `Grid<Cell>`: every cell of a fixed [`Grid`], some of which may be [`Cell::Void`]
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+
+use super::grid::{Grid, Pos};
+
+/// Which neighbour counts bring a dead cell to life, and which keep a live cell alive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new<B, S>(birth: B, survival: S) -> Self
+    where
+        B: IntoIterator<Item = usize>,
+        S: IntoIterator<Item = usize>,
+    {
+        Rule {
+            birth: birth.into_iter().collect(),
+            survival: survival.into_iter().collect(),
+        }
+    }
+
+    /// Conway's Game of Life: born with 3 live neighbours, survives with 2 or 3
+    pub fn life() -> Self {
+        Rule::new(vec![3], 2..=3)
+    }
+
+    /// Whether a cell is alive in the next generation
+    pub fn next(&self, alive: bool, neighbors: usize) -> bool {
+        if alive {
+            self.survival.contains(&neighbors)
+        } else {
+            self.birth.contains(&neighbors)
+        }
+    }
+}
+
+/// Storage for the cells of an automaton
+pub trait Backend: Clone + Eq {
+    type Coord;
+
+    fn is_alive(&self, coord: &Self::Coord) -> bool;
+
+    /// The number of live cells
+    fn population(&self) -> usize;
+
+    /// The cells in the next generation
+    fn next<N, I>(&self, rule: &Rule, neighbors: &N) -> Self
+    where
+        N: Fn(&Self::Coord) -> I,
+        I: IntoIterator<Item = Self::Coord>;
+}
+
+/// The live cells on an unbounded space.
+///
+/// Only cells next to a live cell are considered for birth, so a rule's birth on 0 neighbours
+/// never applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparse<C: Hash + Eq>(HashSet<C>);
+
+impl<C: Hash + Eq> Sparse<C> {
+    pub fn new() -> Self {
+        Sparse(HashSet::new())
+    }
+
+    pub fn insert(&mut self, coord: C) -> bool {
+        self.0.insert(coord)
+    }
+
+    pub fn remove(&mut self, coord: &C) -> bool {
+        self.0.remove(coord)
+    }
+
+    pub fn contains(&self, coord: &C) -> bool {
+        self.0.contains(coord)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &C> {
+        self.0.iter()
+    }
+}
+
+impl<C: Hash + Eq> Default for Sparse<C> {
+    fn default() -> Self {
+        Sparse::new()
+    }
+}
+
+impl<C: Hash + Eq> FromIterator<C> for Sparse<C> {
+    fn from_iter<T: IntoIterator<Item = C>>(iter: T) -> Self {
+        Sparse(iter.into_iter().collect())
+    }
+}
+
+/// Hashes the same regardless of the order the cells are stored in
+impl<C: Hash + Eq> Hash for Sparse<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let sum = self.0.iter().fold(0u64, |sum, coord| {
+            let mut hasher = DefaultHasher::new();
+            coord.hash(&mut hasher);
+            sum.wrapping_add(hasher.finish())
+        });
+        state.write_usize(self.0.len());
+        state.write_u64(sum);
+    }
+}
+
+impl<C: Hash + Eq + Clone> Backend for Sparse<C> {
+    type Coord = C;
+
+    fn is_alive(&self, coord: &C) -> bool {
+        self.contains(coord)
+    }
+
+    fn population(&self) -> usize {
+        self.0.len()
+    }
+
+    fn next<N, I>(&self, rule: &Rule, neighbors: &N) -> Self
+    where
+        N: Fn(&C) -> I,
+        I: IntoIterator<Item = C>,
+    {
+        let mut counts: HashMap<C, usize> = HashMap::new();
+        for coord in self.0.iter() {
+            for neighbor in neighbors(coord) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        let isolated = self
+            .0
+            .iter()
+            .filter(|&coord| !counts.contains_key(coord) && rule.next(true, 0))
+            .cloned()
+            .collect::<Vec<_>>();
+        counts
+            .into_iter()
+            .filter(|(coord, count)| rule.next(self.contains(coord), *count))
+            .map(|(coord, _)| coord)
+            .chain(isolated)
+            .collect()
+    }
+}
+
+/// A cell in a `Grid` backed automaton
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Cell {
+    #[default]
+    Dead,
+    Alive,
+    /// A position that never comes alive, such as a wall
+    Void,
+}
+
+impl Backend for Grid<Cell> {
+    type Coord = Pos;
+
+    fn is_alive(&self, pos: &Pos) -> bool {
+        self.get(*pos) == Some(&Cell::Alive)
+    }
+
+    fn population(&self) -> usize {
+        self.iter().filter(|&&cell| cell == Cell::Alive).count()
+    }
+
+    fn next<N, I>(&self, rule: &Rule, neighbors: &N) -> Self
+    where
+        N: Fn(&Pos) -> I,
+        I: IntoIterator<Item = Pos>,
+    {
+        let cells = self
+            .enumerate()
+            .map(|(pos, &cell)| {
+                if cell == Cell::Void {
+                    return Cell::Void;
+                }
+                let count = neighbors(&pos)
+                    .into_iter()
+                    .filter(|n| self.is_alive(n))
+                    .count();
+                if rule.next(cell == Cell::Alive, count) {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                }
+            })
+            .collect();
+        Grid::from_vec(cells, self.cols())
+    }
+}
+
+/// Where an automaton's generations start repeating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation that's repeated
+    pub start: usize,
+    /// How many generations pass before it repeats
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest generation with the same cells as `generation`
+    pub fn equivalent(&self, generation: usize) -> usize {
+        if generation < self.start {
+            generation
+        } else {
+            self.start + (generation - self.start) % self.period
+        }
+    }
+}
+
+/// Cells stored in `B`, with neighbours found by `N`
+#[derive(Debug, Clone)]
+pub struct Automaton<B, N> {
+    cells: B,
+    rule: Rule,
+    neighbors: N,
+    generation: usize,
+}
+
+impl<B, N, I> Automaton<B, N>
+where
+    B: Backend,
+    N: Fn(&B::Coord) -> I,
+    I: IntoIterator<Item = B::Coord>,
+{
+    pub fn new(cells: B, rule: Rule, neighbors: N) -> Self {
+        Automaton {
+            cells,
+            rule,
+            neighbors,
+            generation: 0,
+        }
+    }
+
+    pub fn cells(&self) -> &B {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> B {
+        self.cells
+    }
+
+    /// How many steps have been run
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.population()
+    }
+
+    /// Advance a generation, returning whether any cell changed
+    pub fn step(&mut self) -> bool {
+        let next = self.cells.next(&self.rule, &self.neighbors);
+        self.generation += 1;
+        let changed = next != self.cells;
+        self.cells = next;
+        changed
+    }
+
+    /// Advance `generations` generations
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Step until a generation leaves the cells unchanged, returning the generation reached.
+    ///
+    /// Never returns for automata that don't settle.
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() {}
+        self.generation
+    }
+
+    /// Step until the cells repeat an earlier generation.
+    ///
+    /// Never returns for automata that grow forever.
+    pub fn run_until_cycle(&mut self) -> Cycle
+    where
+        B: Hash,
+    {
+        let mut seen = HashMap::new();
+        loop {
+            if let Some(&start) = seen.get(&self.cells) {
+                return Cycle {
+                    start,
+                    period: self.generation - start,
+                };
+            }
+            seen.insert(self.cells.clone(), self.generation);
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn moore((x, y): &(i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+        let (x, y) = (*x, *y);
+        (-1..=1)
+            .flat_map(move |dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+            .filter(move |&p| p != (x, y))
+    }
+
+    #[test]
+    fn test_rule() {
+        let life = Rule::life();
+        assert!(life.next(false, 3));
+        assert!(!life.next(false, 2));
+        assert!(life.next(true, 2));
+        assert!(!life.next(true, 4));
+        assert!(Rule::new(0..=0, vec![]).next(false, 0));
+    }
+
+    #[test]
+    fn test_sparse() {
+        let blinker: Sparse<_> = vec![(0, -1), (0, 0), (0, 1)].into_iter().collect();
+        let mut life = Automaton::new(blinker.clone(), Rule::life(), moore);
+
+        assert!(life.step());
+        assert_eq!(1, life.generation());
+        assert_eq!(3, life.population());
+        assert!(life.cells().contains(&(1, 0)));
+        assert!(!life.cells().contains(&(0, 1)));
+
+        life.step();
+        assert_eq!(&blinker, life.cells());
+
+        let mut lonely = Automaton::new(blinker, Rule::new(vec![], 0..=2), moore);
+        lonely.run(3);
+        assert_eq!(3, lonely.population());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let glider: Sparse<_> = vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+            .into_iter()
+            .collect();
+        let mut dying = Automaton::new(glider, Rule::new(vec![], vec![2]), moore);
+        assert_eq!(3, dying.run_until_stable());
+        assert_eq!(0, dying.population());
+
+        let blinker: Sparse<_> = vec![(0, -1), (0, 0), (0, 1), (10, 10)]
+            .into_iter()
+            .collect();
+        let mut life = Automaton::new(blinker, Rule::life(), moore);
+        let cycle = life.run_until_cycle();
+        assert_eq!(
+            Cycle {
+                start: 1,
+                period: 2
+            },
+            cycle
+        );
+        assert_eq!(3, life.generation());
+        assert_eq!(0, cycle.equivalent(0));
+        assert_eq!(1, cycle.equivalent(101));
+        assert_eq!(2, cycle.equivalent(1000));
+    }
+
+    #[test]
+    fn test_dense() {
+        let cells = Grid::parse_with(".#.\n.#.\n.#X", |c| match c {
+            '#' => Ok(Cell::Alive),
+            '.' => Ok(Cell::Dead),
+            'X' => Ok(Cell::Void),
+            _ => Err("unknown cell"),
+        })
+        .unwrap();
+        let bounds = cells.clone();
+        let mut life = Automaton::new(cells, Rule::life(), |&pos: &Pos| bounds.neighbors8(pos));
+
+        life.step();
+        assert_eq!(&[Cell::Alive; 3], life.cells().row(1));
+        assert_eq!(&[Cell::Dead, Cell::Dead, Cell::Void], life.cells().row(2));
+        assert_eq!(3, life.population());
+        assert_eq!(
+            Cycle {
+                start: 1,
+                period: 2
+            },
+            life.run_until_cycle()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! A local cache of puzzle inputs laid out as `<dir>/<year>/day<NN>.txt`.
+//!
+//! Imported inputs get a `day<NN>.txt.sha256` file next to them in `sha256sum` format, recording
+//! which input the answers for that day were found with. Reading an input checks it against that
+//! file so an edited or replaced input doesn't go unnoticed.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use super::error::Error;
+
+/// Environment variable naming the cache directory
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Cache directory used when [`INPUT_DIR_VAR`] isn't set
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Location of the input for a given day within an input directory
+pub fn input_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Location of the checksum recorded for a day's input
+pub fn checksum_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    let mut path = input_path(dir, year, day).into_os_string();
+    path.push(".sha256");
+    path.into()
+}
+
+/// Hex encoded SHA-256 of `input`
+pub fn checksum(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Copy `input` into the cache and record its checksum, replacing any previous input for the day.
+/// Returns the checksum.
+pub fn import(dir: &Path, year: u16, day: u8, input: &str) -> Result<String, Error> {
+    let path = input_path(dir, year, day);
+    let sum = checksum(input);
+    let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+    let write = |path: &Path, contents: &str| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, contents))
+            .map_err(|source| Error::Input {
+                path: path.to_path_buf(),
+                source,
+            })
+    };
+    write(&path, input)?;
+    write(
+        &checksum_path(dir, year, day),
+        &format!("{}  {}\n", sum, file_name),
+    )?;
+    Ok(sum)
+}
+
+/// Read a day's input from the cache, checking it against its recorded checksum if it has one
+pub fn read(dir: &Path, year: u16, day: u8) -> Result<String, Error> {
+    let path = input_path(dir, year, day);
+    let input = fs::read_to_string(&path).map_err(|source| Error::Input {
+        path: path.clone(),
+        source,
+    })?;
+
+    let sum_path = checksum_path(dir, year, day);
+    let expected = match fs::read_to_string(&sum_path) {
+        Ok(s) => s.split_whitespace().next().unwrap_or_default().to_string(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(input),
+        Err(source) => {
+            return Err(Error::Input {
+                path: sum_path,
+                source,
+            })
+        }
+    };
+    let actual = checksum(&input);
+    if actual != expected {
+        return Err(Error::Checksum {
+            path,
+            expected,
+            actual,
+        });
+    }
+    Ok(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            checksum("abc")
+        );
+    }
+
+    #[test]
+    fn test_import_and_read() {
+        let dir = env::temp_dir().join(format!("aoclib-cache-{}", std::process::id()));
+
+        let sum = import(&dir, 2020, 5, "abc").unwrap();
+        assert_eq!(checksum("abc"), sum);
+        assert_eq!(
+            format!("{}  day05.txt\n", sum),
+            fs::read_to_string(checksum_path(&dir, 2020, 5)).unwrap()
+        );
+        assert_eq!("abc", read(&dir, 2020, 5).unwrap());
+
+        fs::write(input_path(&dir, 2020, 5), "abd").unwrap();
+        let err = read(&dir, 2020, 5);
+
+        // Inputs placed in the cache by hand have nothing to check against
+        fs::write(input_path(&dir, 2020, 6), "xyz").unwrap();
+        let unchecked = read(&dir, 2020, 6);
+        let missing = read(&dir, 2020, 7);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(err, Err(Error::Checksum { expected, .. }) if expected == sum));
+        assert_eq!("xyz", unchecked.unwrap());
+        assert!(matches!(missing, Err(Error::Input { .. })));
+    }
+}
@@ -0,0 +1,544 @@
+use std::fmt;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use super::answers::{Answers, Verdict};
+use super::cache;
+use super::render::{self, Format};
+use super::source::{CacheSource, FileSource, HttpSource, InputSource};
+use super::submit::{self, HttpSubmitter, Outcome, Response};
+use super::{Error, PartResult, Timing, YearResult, AOC};
+
+/// Exit status when a part failed to solve, an answer didn't match the recorded one or a submitted
+/// answer wasn't accepted
+pub const EXIT_SOLVER: i32 = 1;
+/// Exit status for invalid arguments, including asking for a year or day which isn't registered
+pub const EXIT_USAGE: i32 = 2;
+/// Exit status when an input or answers file can't be read
+pub const EXIT_INPUT: i32 = 3;
+
+/// Parse command line arguments, load the requested input and print the results of running `aoc`.
+///
+/// Results go to stdout. Errors go to stderr and the process exits with one of the `EXIT_*`
+/// statuses, giving priority to usage errors, then unreadable inputs, then failed parts.
+pub fn run(aoc: AOC) {
+    let m = App::new("Advent of Code solvers")
+        .author("Kyle Willmon <kylewillmon@gmail.com>")
+        .arg(Arg::from_usage(
+            "[INPUT] 'Sets the input file to use, or - for stdin. Defaults to the cached input'",
+        ))
+        .arg(
+            Arg::with_name("year")
+                .short("y")
+                .long("year")
+                .takes_value(true)
+                .help("year to solve"),
+        )
+        .arg(
+            Arg::with_name("day")
+                .short("d")
+                .long("day")
+                .takes_value(true)
+                .help("day to solve"),
+        )
+        .arg(
+            Arg::with_name("all")
+                .short("a")
+                .long("all")
+                .conflicts_with_all(&["INPUT", "day"])
+                .help("solve every registered day"),
+        )
+        .arg(
+            Arg::with_name("list")
+                .short("l")
+                .long("list")
+                .conflicts_with_all(&["INPUT", "day", "all"])
+                .help("list every registered day and part, with the defaults of their parameters"),
+        )
+        .arg(
+            Arg::with_name("import")
+                .long("import")
+                .takes_value(true)
+                .value_name("FILE")
+                .requires_all(&["year", "day"])
+                .conflicts_with_all(&["INPUT", "all", "list"])
+                .help("copy FILE (or - for stdin) into the input cache and record its checksum"),
+        )
+        .arg(
+            Arg::with_name("fetch")
+                .long("fetch")
+                .requires_all(&["year", "day"])
+                .conflicts_with_all(&["INPUT", "all", "list", "import"])
+                .help("download the input into the input cache, using the session token in $AOC_SESSION or the aoc/session config file"),
+        )
+        .arg(
+            Arg::with_name("time")
+                .short("t")
+                .long("time")
+                .help("report how long each part takes"),
+        )
+        .arg(
+            Arg::with_name("bench")
+                .long("bench")
+                .takes_value(true)
+                .value_name("N")
+                .conflicts_with("time")
+                .help("solve each part N times and report min/median/mean durations"),
+        )
+        .arg(
+            Arg::with_name("timeout")
+                .long("timeout")
+                .takes_value(true)
+                .value_name("SECS")
+                .help(
+                    "give up on any part which takes longer than SECS seconds. Parts which don't \
+                     check for cancellation keep running in the background until they finish",
+                ),
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .short("j")
+                .long("jobs")
+                .takes_value(true)
+                .value_name("N")
+                .requires("all")
+                .help("solve up to N days at once with --all (timings will be noisier)"),
+        )
+        .arg(param_arg())
+        .arg(
+            Arg::with_name("format")
+                .short("f")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["text", "json", "csv"])
+                .default_value("text")
+                .help("output format"),
+        )
+        .arg(
+            Arg::with_name("verify")
+                .long("verify")
+                .help("check answers against <answers-dir>/<year>.toml and fail on mismatch"),
+        )
+        .arg(answers_dir_arg())
+        .arg(input_dir_arg())
+        .subcommand(
+            SubCommand::with_name("submit")
+                .about("solve a part and submit its answer, unless it's already known to be right or wrong")
+                .arg(Arg::from_usage(
+                    "[INPUT] 'Sets the input file to use, or - for stdin. Defaults to the cached input'",
+                ))
+                .arg(Arg::from_usage("-y, --year <YEAR> 'year to submit'"))
+                .arg(Arg::from_usage("-d, --day <DAY> 'day to submit'"))
+                .arg(Arg::from_usage("--part <PART> 'part to submit'"))
+                .arg(answers_dir_arg())
+                .arg(input_dir_arg()),
+        )
+        .get_matches_safe()
+        .unwrap_or_else(|e| match e.kind {
+            clap::ErrorKind::HelpDisplayed | clap::ErrorKind::VersionDisplayed => e.exit(),
+            _ => {
+                // clap's message already says it's an error and how to get help
+                eprintln!("{}", e.message);
+                process::exit(EXIT_USAGE)
+            }
+        });
+
+    if let Some(sub) = m.subcommand_matches("submit") {
+        if m.is_present("param") {
+            // An answer found with overridden parameters isn't the puzzle's, and the site would
+            // record it as a wrong answer
+            fail(EXIT_USAGE, "--param can't be used with submit");
+        }
+        return submit(aoc, sub);
+    }
+
+    let year = parse_arg::<u16>(&m, "year", "year");
+    let day = parse_arg::<u8>(&m, "day", "day");
+
+    if m.is_present("list") {
+        let years: Vec<_> = aoc
+            .years()
+            .iter()
+            .filter(|y| year.is_none_or(|year| y.year == year))
+            .collect();
+        if years.is_empty() {
+            fail(EXIT_USAGE, Error::YearNotFound);
+        }
+        print!("{}", render::list(years));
+        return;
+    }
+
+    let input_dir = Path::new(m.value_of("input-dir").unwrap());
+
+    let import: Option<Box<dyn InputSource>> = if let Some(filename) = m.value_of("import") {
+        Some(Box::new(FileSource::new(filename)))
+    } else if m.is_present("fetch") {
+        match HttpSource::from_config() {
+            Ok(source) => Some(Box::new(source)),
+            Err(err) => fail(exit_code(&err), err),
+        }
+    } else {
+        None
+    };
+    if let Some(source) = import {
+        let (year, day) = (year.unwrap(), day.unwrap());
+        let imported = source
+            .fetch(year, day)
+            .and_then(|input| cache::import(input_dir, year, day, &input));
+        match imported {
+            Ok(sum) => println!(
+                "Imported {} (sha256 {})",
+                cache::input_path(input_dir, year, day).display(),
+                sum
+            ),
+            Err(err) => fail(exit_code(&err), err),
+        }
+        return;
+    }
+
+    let timing = match m.value_of("bench") {
+        None if m.is_present("time") => Timing::Once,
+        None => Timing::Off,
+        Some(val) => match val.parse::<u32>() {
+            Ok(n) if n > 0 => Timing::Bench(n),
+            Ok(_) => {
+                fail(
+                    EXIT_USAGE,
+                    format!("Invalid bench count {:?}: must be at least 1", val),
+                );
+            }
+            Err(err) => {
+                fail(
+                    EXIT_USAGE,
+                    format!("Invalid bench count {:?}: {}", val, err),
+                );
+            }
+        },
+    };
+    let mut aoc = aoc.timing(timing);
+
+    if let Some(val) = m.value_of("timeout") {
+        match val.parse::<f64>().map(Duration::try_from_secs_f64) {
+            Ok(Ok(timeout)) => aoc = aoc.timeout(timeout),
+            Ok(Err(err)) => {
+                fail(EXIT_USAGE, format!("Invalid timeout {:?}: {}", val, err));
+            }
+            Err(err) => {
+                fail(EXIT_USAGE, format!("Invalid timeout {:?}: {}", val, err));
+            }
+        }
+    }
+    let time = timing != Timing::Off;
+
+    if let Some(val) = m.value_of("jobs") {
+        match val.parse::<usize>() {
+            Ok(jobs) => aoc = aoc.jobs(jobs),
+            Err(err) => {
+                fail(EXIT_USAGE, format!("Invalid job count {:?}: {}", val, err));
+            }
+        }
+    }
+
+    let aoc = with_params(aoc, &m);
+
+    let format: Format = m.value_of("format").unwrap().parse().unwrap();
+
+    let all = m.is_present("all");
+    let results = if all {
+        aoc.run_all(year, input_dir)
+    } else if let Some(filename) = m.value_of("INPUT") {
+        aoc.run_from(year, day, &FileSource::new(filename))
+            .map(|r| vec![r])
+    } else {
+        aoc.run_from(year, day, &CacheSource::new(input_dir))
+            .map(|r| vec![r])
+    };
+
+    let mut results = match results {
+        Ok(results) => results,
+        Err(err) => fail(exit_code(&err), err),
+    };
+
+    let verify = m.is_present("verify");
+    if verify {
+        let dir = Path::new(m.value_of("answers-dir").unwrap());
+        for y in results.iter_mut() {
+            match Answers::load(dir, y.year) {
+                Ok(answers) => answers.verify(y),
+                Err(err) => fail(EXIT_INPUT, format!("{:#}", err)),
+            }
+        }
+    }
+
+    let out = match format {
+        Format::Text if all => render::table(&results, time),
+        Format::Text => render::text(&results, time),
+        Format::Json => render::json(&results),
+        Format::Csv => render::csv(&results),
+    };
+    print!("{}", out);
+
+    let status = report_failures(&results);
+    if status != 0 {
+        process::exit(status);
+    }
+}
+
+fn param_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("param")
+        .short("p")
+        .long("param")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("NAME=VALUE")
+        .help("override a parameter of the solvers, e.g. to run them against an example")
+}
+
+fn answers_dir_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("answers-dir")
+        .long("answers-dir")
+        .takes_value(true)
+        .default_value("answers")
+        .help("directory of recorded answers, used by --verify and submit")
+}
+
+fn input_dir_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("input-dir")
+        .long("input-dir")
+        .takes_value(true)
+        .env(cache::INPUT_DIR_VAR)
+        .default_value(cache::DEFAULT_INPUT_DIR)
+        .help("input cache of <year>/day<NN>.txt files, used when INPUT isn't given")
+}
+
+/// Parse the value of `name`, if it was given, failing with a usage error if it's invalid
+fn parse_arg<T>(m: &ArgMatches, name: &str, what: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    m.value_of(name).map(|val| match val.parse() {
+        Ok(val) => val,
+        Err(err) => fail(EXIT_USAGE, format!("Invalid {} {:?}: {}", what, val, err)),
+    })
+}
+
+/// Apply every `--param NAME=VALUE` to `aoc`
+fn with_params(mut aoc: AOC, m: &ArgMatches) -> AOC {
+    for val in m.values_of("param").into_iter().flatten() {
+        match val.split_once('=') {
+            Some((name, value)) if !name.is_empty() => aoc = aoc.param(name, value),
+            _ => {
+                fail(
+                    EXIT_USAGE,
+                    format!("Invalid parameter {:?}: expected NAME=VALUE", val),
+                );
+            }
+        }
+    }
+    aoc
+}
+
+/// Solve the part asked for by the `submit` subcommand and submit its answer
+fn submit(aoc: AOC, m: &ArgMatches) {
+    let year = parse_arg::<u16>(m, "year", "year").unwrap();
+    let day = parse_arg::<u8>(m, "day", "day").unwrap();
+    let part = parse_arg::<u8>(m, "part", "part").unwrap();
+    let aoc = aoc.part(part);
+
+    let result = match m.value_of("INPUT") {
+        Some(filename) => aoc.run_from(Some(year), Some(day), &FileSource::new(filename)),
+        None => {
+            let dir = m.value_of("input-dir").unwrap();
+            aoc.run_from(Some(year), Some(day), &CacheSource::new(dir))
+        }
+    };
+    let result = result.unwrap_or_else(|err| fail(exit_code(&err), err));
+    let solved = result.days.iter().flatten().next();
+    let answer = match solved
+        .into_iter()
+        .flat_map(|d| d.parts.iter())
+        .find(|p| p.part == part)
+    {
+        Some(PartResult {
+            answer: Ok(answer), ..
+        }) => answer,
+        Some(PartResult {
+            answer: Err(err), ..
+        }) => fail(
+            EXIT_SOLVER,
+            format!("{} day {} part {}: {:#}", year, day, part, err),
+        ),
+        None => fail(
+            EXIT_USAGE,
+            format!("{} day {} has no part {}", year, day, part),
+        ),
+    };
+
+    let submitter = HttpSubmitter::from_config().unwrap_or_else(|err| fail(exit_code(&err), err));
+    let dir = Path::new(m.value_of("answers-dir").unwrap());
+    let checksum = solved.map_or("", |d| &d.checksum);
+    let outcome = submit::submit(&submitter, dir, year, day, part, answer, checksum);
+    let outcome = outcome.unwrap_or_else(|err| {
+        let code = err.downcast_ref::<Error>().map_or(EXIT_INPUT, exit_code);
+        fail(code, format!("{:#}", err))
+    });
+
+    let what = format!("{} day {} part {}", year, day, part);
+    match outcome {
+        Outcome::Sent(Response::Correct) => println!("{}: {} is correct", what, answer),
+        Outcome::Sent(response) => {
+            fail(EXIT_SOLVER, format!("{}: {} is {}", what, answer, response))
+        }
+        Outcome::AlreadyAccepted(accepted) if &accepted == answer => {
+            println!("{}: {} was already accepted", what, answer)
+        }
+        Outcome::AlreadyAccepted(accepted) => fail(
+            EXIT_SOLVER,
+            format!(
+                "{}: {} differs from the accepted answer {}",
+                what, answer, accepted
+            ),
+        ),
+        Outcome::KnownWrong(reason) => {
+            fail(EXIT_SOLVER, format!("{}: not submitting, {}", what, reason))
+        }
+        Outcome::InputMismatch(recorded) => fail(
+            EXIT_INPUT,
+            format!(
+                "{}: not submitting, the input has checksum {} but the day's answers were \
+                 recorded for {}",
+                what, checksum, recorded
+            ),
+        ),
+    }
+}
+
+/// Print `msg` to stderr and exit with `code`
+fn fail<T: fmt::Display>(code: i32, msg: T) -> ! {
+    eprintln!("Error: {}", msg);
+    process::exit(code)
+}
+
+fn exit_code(err: &Error) -> i32 {
+    match err {
+        Error::Input { .. }
+        | Error::Checksum { .. }
+        | Error::Fetch { .. }
+        | Error::Submit { .. }
+        | Error::NoSession { .. } => EXIT_INPUT,
+        Error::YearNotFound | Error::DayNotFound { .. } | Error::UnknownParam(_) => EXIT_USAGE,
+    }
+}
+
+/// Print a line to stderr for every unreadable or mismatched input, failed part and wrong answer in
+/// `results`, and return the status to exit with.
+///
+/// Days skipped for having no input at all aren't failures, the output already notes them.
+fn report_failures(results: &[YearResult]) -> i32 {
+    let mut status = 0;
+    for y in results {
+        for d in y.days.iter() {
+            let d = match d {
+                Ok(d) => d,
+                Err((_, err)) if err.is_missing_input() => continue,
+                Err((day, err)) => {
+                    eprintln!("Error: {} day {}: {}", y.year, day, err);
+                    status = EXIT_INPUT;
+                    continue;
+                }
+            };
+            let mismatch = d.parts.iter().find_map(|p| match &p.verdict {
+                Some(Verdict::InputMismatch { expected }) => Some(expected),
+                _ => None,
+            });
+            if let Some(expected) = mismatch {
+                eprintln!(
+                    "Error: {} day {}: input has checksum {}, but the answers were recorded for {}",
+                    y.year, d.day, d.checksum, expected
+                );
+                status = EXIT_INPUT;
+                continue;
+            }
+            for p in d.parts.iter() {
+                let failure = match (&p.answer, &p.verdict) {
+                    (Err(err), _) => format!("{:#}", err),
+                    (Ok(answer), Some(Verdict::Fail { expected })) => {
+                        format!("answer {} doesn't match expected {}", answer, expected)
+                    }
+                    _ => continue,
+                };
+                eprintln!(
+                    "Error: {} day {} part {}: {}",
+                    y.year, d.day, p.part, failure
+                );
+                if status == 0 {
+                    status = EXIT_SOLVER;
+                }
+            }
+        }
+    }
+    status
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_exit_code() {
+        let io_error = || io::Error::from(io::ErrorKind::PermissionDenied);
+        let cases = vec![
+            (Error::YearNotFound, EXIT_USAGE),
+            (
+                Error::DayNotFound {
+                    year: 2020,
+                    day: Some(9),
+                    nearest: vec![8],
+                },
+                EXIT_USAGE,
+            ),
+            (Error::UnknownParam("turns".into()), EXIT_USAGE),
+            (
+                Error::Input {
+                    path: PathBuf::from("day01.txt"),
+                    source: io_error(),
+                },
+                EXIT_INPUT,
+            ),
+            (
+                Error::Fetch {
+                    url: "https://adventofcode.com".into(),
+                    reason: "timed out".into(),
+                },
+                EXIT_INPUT,
+            ),
+            (
+                Error::Submit {
+                    url: "https://adventofcode.com".into(),
+                    reason: "HTTP 500".into(),
+                },
+                EXIT_INPUT,
+            ),
+            (Error::NoSession { path: None }, EXIT_INPUT),
+            (
+                Error::Checksum {
+                    path: PathBuf::from("day01.txt"),
+                    expected: "abc".into(),
+                    actual: "def".into(),
+                },
+                EXIT_INPUT,
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(code, exit_code(&err), "{:?}", err);
+        }
+    }
+}
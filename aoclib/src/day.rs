@@ -1,59 +1,646 @@
-use std::fmt::Write;
+use std::any::Any;
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use anyhow::anyhow;
+
+use super::answers::Verdict;
+use super::cache;
+use super::error::Error;
+use super::options::Options;
+use super::params::Params;
 use super::solver;
+use super::timing::{Stats, Timing};
 
-pub struct Day<'a> {
+pub struct Day {
     pub day: u8,
-    parts: Vec<Part<'a>>,
+    parser: Option<Arc<solver::Parser>>,
+    parts: Vec<Part>,
+    /// Default parameters of each part
+    params: HashMap<u8, Params>,
 }
 
-impl<'a> Day<'a> {
+impl Day {
     pub fn new(day: u8) -> Self {
         Day {
             day,
+            parser: None,
             parts: Vec::new(),
+            params: HashMap::new(),
         }
     }
 
     pub fn part<F>(mut self, part: u8, solver: F) -> Self
     where
-        F: 'a + solver::Solver,
+        F: 'static + solver::Solver,
     {
-        self.parts.push(Part::new(part, solver));
+        self.parts.push(Part::new(part, solver, false));
         self
     }
 
-    pub fn solve(self, input: String) -> String {
-        let mut out = String::new();
-        let input = input.replace("\r\n", "\n");
-        for part in self.parts.into_iter() {
-            writeln!(out, "Part: {}", part.part).unwrap();
-            match part.solve(input.clone()) {
-                Ok(solution) => writeln!(out, "Solution: {}", solution).unwrap(),
-                Err(e) => writeln!(out, "Error: {}", e).unwrap(),
+    /// Parse the input once and hand the result to every part added to the returned [`ParsedDay`]
+    pub fn parse<T, F, E>(mut self, parser: F) -> ParsedDay<T>
+    where
+        T: 'static + Send + Sync,
+        F: 'static + Fn(&str) -> Result<T, E> + Send + Sync,
+        E: Into<anyhow::Error>,
+    {
+        self.parser = Some(Arc::new(move |input: &str| {
+            parser(input)
+                .map(|v| Arc::new(v) as Arc<dyn Any + Send + Sync>)
+                .map_err(Into::into)
+        }));
+        ParsedDay {
+            day: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Add a part whose solver only borrows the input, avoiding a copy of it for each part
+    pub fn part_str<F>(self, part: u8, solver: F) -> Self
+    where
+        solver::Borrowed<F>: 'static + solver::Solver,
+    {
+        self.part(part, solver::Borrowed(solver))
+    }
+
+    /// Add a part whose solver borrows the input and takes the part's parameters
+    pub fn part_with_params<F>(self, part: u8, solver: F) -> Self
+    where
+        solver::WithParams<F>: 'static + solver::Solver,
+    {
+        self.part(part, solver::WithParams(solver))
+    }
+
+    /// Add a part whose solver borrows the input and polls a [`Cancel`] flag, which is set if the
+    /// part times out
+    ///
+    /// [`Cancel`]: crate::Cancel
+    pub fn part_cancellable<F>(self, part: u8, solver: F) -> Self
+    where
+        solver::Cancellable<F>: 'static + solver::Solver,
+    {
+        self.part(part, solver::Cancellable(solver))
+    }
+
+    /// Declare a parameter of `part` along with the value the real puzzle uses.
+    ///
+    /// Only declared parameters can be overridden through [`Options::params`].
+    pub fn param<V: fmt::Display>(mut self, part: u8, name: &str, default: V) -> Self {
+        self.params.entry(part).or_default().insert(name, default);
+        self
+    }
+
+    /// The numbers of the day's parts, in the order they were added
+    pub fn parts(&self) -> impl Iterator<Item = u8> + '_ {
+        self.parts.iter().map(|p| p.part)
+    }
+
+    /// The parameters declared for `part` and their defaults
+    pub fn params(&self, part: u8) -> Option<&Params> {
+        self.params.get(&part)
+    }
+
+    /// Check that every parameter in `params` is declared by at least one part
+    pub fn check_params(&self, params: &Params) -> Result<(), Error> {
+        check_declared(std::iter::once(self), params)
+    }
+
+    fn declares(&self, name: &str) -> bool {
+        self.params.values().any(|p| p.contains(name))
+    }
+
+    /// The defaults declared for `part`, with any of them overridden by `overrides`
+    fn part_params(&self, part: u8, overrides: &Params) -> Params {
+        let mut params = self.params.get(&part).cloned().unwrap_or_default();
+        for (name, value) in overrides.iter() {
+            if params.contains(name) {
+                params.insert(name, value);
             }
         }
-        out
+        params
     }
+
+    /// Solve each part in order (or only [`Options::part`]), timing every run.
+    ///
+    /// If the day has a parser it runs first. Parts which need its output fail if it does.
+    pub fn solve(self, input: String, options: Options) -> DayResult {
+        let checksum = cache::checksum(&input);
+        let mut input = solver::Input {
+            raw: input.replace("\r\n", "\n").into(),
+            parsed: None,
+            params: Params::new(),
+            cancel: solver::Cancel::new(),
+        };
+
+        let mut parse = None;
+        let mut parse_error = None;
+        if let Some(parser) = &self.parser {
+            let parser = Arc::clone(parser);
+            let raw = Arc::clone(&input.raw);
+            let cancel = solver::Cancel::new();
+            let (parsed, duration) = run_caught(move || parser(&raw), options.timeout, &cancel);
+            parse = Some(duration);
+            match parsed {
+                Ok(parsed) => input.parsed = Some(parsed),
+                Err(e) => parse_error = Some(format!("failed to parse input: {:#}", e)),
+            }
+        }
+
+        let parts = self
+            .parts
+            .iter()
+            .filter(|part| options.part.is_none_or(|p| p == part.part))
+            .map(|part| match &parse_error {
+                Some(e) if part.parsed => PartResult {
+                    part: part.part,
+                    answer: Err(anyhow!("{}", e)),
+                    duration: Duration::default(),
+                    bench: None,
+                    verdict: None,
+                },
+                _ => {
+                    let input = solver::Input {
+                        params: self.part_params(part.part, &options.params),
+                        ..input.clone()
+                    };
+                    part.solve(&input, &options)
+                }
+            })
+            .collect();
+
+        DayResult {
+            day: self.day,
+            checksum,
+            parse,
+            parts,
+        }
+    }
+}
+
+/// Check that every parameter in `params` is declared by at least one part of one of `days`
+pub(crate) fn check_declared<'a, I>(days: I, params: &Params) -> Result<(), Error>
+where
+    I: IntoIterator<Item = &'a Day>,
+    I::IntoIter: Clone,
+{
+    let days = days.into_iter();
+    match params
+        .iter()
+        .find(|(name, _)| !days.clone().any(|d| d.declares(name)))
+    {
+        Some((name, _)) => Err(Error::UnknownParam(name.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// A day whose parts share the output of a parser, see [`Day::parse`]
+pub struct ParsedDay<T> {
+    day: Day,
+    _marker: PhantomData<fn() -> T>,
 }
 
-struct Part<'a> {
+impl<T: 'static + Send + Sync> ParsedDay<T> {
+    /// Add a part which solves the parsed input. The solver may take anything the parser's output
+    /// can be borrowed as, so a `Vec<u64>` can be solved by a function of `&[u64]`.
+    pub fn part<B, F, R, E>(self, part: u8, solver: F) -> Self
+    where
+        T: Borrow<B>,
+        B: ?Sized + 'static,
+        F: 'static + Fn(&B) -> Result<R, E> + Send + Sync,
+        R: fmt::Display,
+        E: Into<anyhow::Error>,
+    {
+        self.part_with_params(part, move |input: &B, _: &Params| solver(input))
+    }
+
+    /// Add a part which solves the parsed input and takes the part's parameters
+    pub fn part_with_params<B, F, R, E>(mut self, part: u8, solver: F) -> Self
+    where
+        T: Borrow<B>,
+        B: ?Sized + 'static,
+        F: 'static + Fn(&B, &Params) -> Result<R, E> + Send + Sync,
+        R: fmt::Display,
+        E: Into<anyhow::Error>,
+    {
+        let solver = solver::Parsed::<T, B, F>::new(solver);
+        self.day.parts.push(Part::new(part, solver, true));
+        self
+    }
+
+    /// See [`Day::param`]
+    pub fn param<V: fmt::Display>(mut self, part: u8, name: &str, default: V) -> Self {
+        self.day = self.day.param(part, name, default);
+        self
+    }
+}
+
+impl<T> From<ParsedDay<T>> for Day {
+    fn from(d: ParsedDay<T>) -> Self {
+        d.day
+    }
+}
+
+/// The answers to every part of a single day
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u8,
+    /// SHA-256 of the input the day was solved with, see [`cache::checksum`]
+    pub checksum: String,
+    /// How long the shared parser took, for days which have one
+    pub parse: Option<Duration>,
+    pub parts: Vec<PartResult>,
+}
+
+/// The answer to a single part along with how long it took to find
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<String, anyhow::Error>,
+    /// Duration of the first run
+    pub duration: Duration,
+    /// Statistics over every run when benchmarking
+    pub bench: Option<Stats>,
+    /// Result of checking the answer against a recorded one, when verifying
+    pub verdict: Option<Verdict>,
+}
+
+struct Part {
     part: u8,
-    solver: Box<dyn solver::Solver + 'a>,
+    solver: Arc<dyn solver::Solver>,
+    /// Whether the solver needs the output of the day's parser
+    parsed: bool,
 }
 
-impl<'a> Part<'a> {
-    fn new<F>(part: u8, solver: F) -> Self
+impl Part {
+    fn new<F>(part: u8, solver: F, parsed: bool) -> Self
     where
-        F: 'a + solver::Solver,
+        F: 'static + solver::Solver,
     {
         Part {
             part,
-            solver: Box::new(solver),
+            solver: Arc::new(solver),
+            parsed,
+        }
+    }
+
+    /// Solve the part as many times as `options.timing` asks for, keeping the first answer.
+    /// Errors are not repeated.
+    fn solve(&self, input: &solver::Input, options: &Options) -> PartResult {
+        let (answer, duration) = self.time(input, options.timeout);
+        let mut durations = vec![duration];
+        if answer.is_ok() {
+            for _ in 1..options.timing.runs() {
+                durations.push(self.time(input, options.timeout).1);
+            }
+        }
+        let bench = match options.timing {
+            Timing::Bench(_) => Stats::new(&durations),
+            _ => None,
+        };
+        PartResult {
+            part: self.part,
+            answer,
+            duration,
+            bench,
+            verdict: None,
+        }
+    }
+
+    /// Solve the part once
+    fn time(
+        &self,
+        input: &solver::Input,
+        timeout: Option<Duration>,
+    ) -> (Result<String, anyhow::Error>, Duration) {
+        let solver = Arc::clone(&self.solver);
+        let cancel = solver::Cancel::new();
+        let input = solver::Input {
+            cancel: cancel.clone(),
+            ..input.clone()
+        };
+        run_caught(move || solver.solve(&input), timeout, &cancel)
+    }
+}
+
+/// Run `f`, turning a panic into an error and timing how long it took.
+///
+/// With a timeout `f` runs on a worker thread. If it doesn't finish in time, `cancel` is set and
+/// the thread is abandoned. It stops as soon as `f` notices the flag, or runs to completion in the
+/// background if `f` never checks it.
+fn run_caught<R, F>(
+    f: F,
+    timeout: Option<Duration>,
+    cancel: &solver::Cancel,
+) -> (Result<R, anyhow::Error>, Duration)
+where
+    R: 'static + Send,
+    F: 'static + FnOnce() -> Result<R, anyhow::Error> + Send,
+{
+    let caught = |f: F| {
+        quiet_panics();
+        let catching = CATCHING.with(|c| c.replace(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        CATCHING.with(|c| c.set(catching));
+        result.unwrap_or_else(|payload| Err(anyhow!("panicked: {}", panic_message(&*payload))))
+    };
+
+    let start = Instant::now();
+    let result = match timeout {
+        None => caught(f),
+        Some(timeout) => {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                // The receiver is gone if we timed out, so there's nobody to tell
+                let _ = tx.send(caught(f));
+            });
+            match rx.recv_timeout(timeout) {
+                Ok(result) => result,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    cancel.cancel();
+                    Err(anyhow!("timed out after {:?}", timeout))
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    Err(anyhow!("worker thread exited without a result"))
+                }
+            }
+        }
+    };
+    (result, start.elapsed())
+}
+
+thread_local! {
+    /// Set while `run_caught` is running something on this thread
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install a panic hook which says nothing about panics caught by `run_caught`, since they're
+/// reported as the part's error, and hands any other panic to the hook it replaced
+fn quiet_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_panic_is_isolated() {
+        let result = Day::new(1)
+            .part(1, |_| -> Result<u32, Infallible> {
+                panic!("No two items add up to 2020!")
+            })
+            .part_str(2, |input: &str| input.trim().parse::<u32>())
+            .part(3, |_| -> Result<u32, Infallible> {
+                panic!("{} is invalid", 99)
+            })
+            .solve("42\n".to_string(), Options::default());
+
+        let answers: Vec<_> = result
+            .parts
+            .iter()
+            .map(|p| match &p.answer {
+                Ok(a) => a.clone(),
+                Err(e) => e.to_string(),
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                "panicked: No two items add up to 2020!",
+                "42",
+                "panicked: 99 is invalid"
+            ],
+            answers
+        );
+    }
+
+    #[test]
+    fn test_single_part() {
+        let options = Options {
+            part: Some(2),
+            ..Options::default()
+        };
+        let result = Day::new(1)
+            .part(1, |_| -> Result<u32, Infallible> {
+                panic!("part 1 was solved")
+            })
+            .part_str(2, |input: &str| input.trim().parse::<u32>())
+            .solve("42\n".to_string(), options);
+
+        assert_eq!(1, result.parts.len());
+        assert_eq!(2, result.parts[0].part);
+        assert_eq!("42", result.parts[0].answer.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let options = Options {
+            timeout: Some(Duration::from_millis(50)),
+            ..Options::default()
+        };
+        let result = Day::new(1)
+            .part(1, |_| -> Result<u32, Infallible> {
+                thread::sleep(Duration::from_secs(5));
+                Ok(1)
+            })
+            .part(2, |_| -> Result<u32, Infallible> { Ok(2) })
+            .part(3, |_| -> Result<u32, Infallible> { panic!("boom") })
+            .solve(String::new(), options);
+
+        let answers: Vec<_> = result
+            .parts
+            .iter()
+            .map(|p| match &p.answer {
+                Ok(a) => a.clone(),
+                Err(e) => e.to_string(),
+            })
+            .collect();
+        assert_eq!(vec!["timed out after 50ms", "2", "panicked: boom"], answers);
+        assert!(result.parts[0].duration < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_timeout_cancels() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let options = Options {
+            timeout: Some(Duration::from_millis(20)),
+            ..Options::default()
+        };
+        let stopped = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stopped);
+        let result = Day::new(1)
+            .part_cancellable(1, move |_: &str, cancel: &solver::Cancel| {
+                let result = loop {
+                    if let Err(e) = cancel.check() {
+                        break Err::<u32, _>(e);
+                    }
+                    thread::sleep(Duration::from_millis(1));
+                };
+                flag.store(true, Ordering::SeqCst);
+                result
+            })
+            .solve(String::new(), options);
+
+        assert_eq!(
+            "timed out after 20ms",
+            result.parts[0].answer.as_ref().unwrap_err().to_string()
+        );
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !stopped.load(Ordering::SeqCst) {
+            assert!(Instant::now() < deadline, "solver kept running");
+            thread::sleep(Duration::from_millis(1));
         }
     }
 
-    fn solve(self, input: String) -> Result<String, anyhow::Error> {
-        self.solver.solve(input)
+    #[test]
+    fn test_parse_shared_between_parts() {
+        let day: Day = Day::new(9)
+            .parse(|input: &str| {
+                input
+                    .lines()
+                    .map(|l| l.parse::<u64>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .part(1, |nums: &[u64]| -> Result<u64, Infallible> {
+                Ok(nums.iter().sum())
+            })
+            .part(2, |nums: &Vec<u64>| -> Result<usize, Infallible> {
+                Ok(nums.len())
+            })
+            .into();
+
+        let result = day.solve("1\n2\n3\n".to_string(), Options::default());
+        assert!(result.parse.is_some());
+        let answers: Vec<_> = result
+            .parts
+            .iter()
+            .map(|p| p.answer.as_ref().unwrap().as_str())
+            .collect();
+        assert_eq!(vec!["6", "3"], answers);
+    }
+
+    #[test]
+    fn test_parse_error_fails_parsed_parts() {
+        let day: Day = Day::new(1)
+            .part(1, |_| -> Result<u32, Infallible> { Ok(1) })
+            .parse(|input: &str| input.trim().parse::<u32>())
+            .part(2, |n: &u32| -> Result<u32, Infallible> { Ok(*n) })
+            .into();
+
+        let result = day.solve("abc".to_string(), Options::default());
+        assert_eq!("1", result.parts[0].answer.as_ref().unwrap());
+        assert_eq!(
+            "failed to parse input: invalid digit found in string",
+            result.parts[1].answer.as_ref().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_params_override_defaults() {
+        let turns = |_: &str, params: &Params| params.get::<u32>("turns");
+        let day = || {
+            Day::new(15)
+                .part_with_params(1, turns)
+                .param(1, "turns", 2020)
+                .part_with_params(2, turns)
+                .param(2, "turns", 30_000_000)
+        };
+
+        let answers = |result: DayResult| -> Vec<String> {
+            result
+                .parts
+                .into_iter()
+                .map(|p| p.answer.unwrap())
+                .collect()
+        };
+        assert_eq!(
+            vec!["2020", "30000000"],
+            answers(day().solve(String::new(), Options::default()))
+        );
+
+        let options = Options {
+            params: Params::new().set("turns", 10).set("other", 1),
+            ..Options::default()
+        };
+        assert_eq!(
+            vec!["10", "10"],
+            answers(day().solve(String::new(), options.clone()))
+        );
+        assert_eq!(
+            "unknown parameter `other`",
+            day().check_params(&options.params).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_run_all_rejects_unknown_params() {
+        use crate::{Year, AOC};
+
+        let turns = |_: &str, params: &Params| params.get::<u32>("turns");
+        let year = |year| {
+            Year::new(year)
+                .day(Day::new(1).part_str(1, str::parse::<u32>))
+                .day(
+                    Day::new(15)
+                        .part_with_params(1, turns)
+                        .param(1, "turns", 2020),
+                )
+        };
+        let options = |name: &str| Options {
+            params: Params::new().set(name, 10),
+            ..Options::default()
+        };
+
+        assert!(year(2020).check_params(&options("turns").params).is_ok());
+        assert_eq!(
+            "unknown parameter `bogus`",
+            year(2020)
+                .run_all(std::path::Path::new("inputs"), options("bogus"))
+                .unwrap_err()
+                .to_string()
+        );
+
+        // Declared by a day in any of the years being solved is enough
+        let aoc = || {
+            AOC::new()
+                .year(year(2020))
+                .year(Year::new(2019).day(Day::new(1).part_str(1, str::parse::<u32>)))
+        };
+        assert_eq!(
+            "unknown parameter `bogus`",
+            aoc()
+                .param("bogus", 1)
+                .run_all(None, "inputs")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(aoc().param("turns", 1).run_all(None, "no-such-dir").is_ok());
     }
 }
@@ -0,0 +1,99 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors that prevent a day from being solved at all
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// No year matched the request
+    YearNotFound,
+    /// No day matched the request. Holds the registered days closest to the one asked for, if
+    /// a specific day was.
+    DayNotFound {
+        year: u16,
+        day: Option<u8>,
+        nearest: Vec<u8>,
+    },
+    /// A parameter was given which the day doesn't declare
+    UnknownParam(String),
+    /// The input for a day could not be read
+    Input { path: PathBuf, source: io::Error },
+    /// An input could not be downloaded
+    Fetch { url: String, reason: String },
+    /// An answer could not be submitted
+    Submit { url: String, reason: String },
+    /// No session token was configured for downloading inputs. Holds the file it could be saved
+    /// in, if there's a config directory to put it in.
+    NoSession { path: Option<PathBuf> },
+    /// A cached input doesn't match the checksum recorded when it was imported
+    Checksum {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Error {
+    /// Whether this is an input that doesn't exist, as opposed to one that couldn't be read
+    pub fn is_missing_input(&self) -> bool {
+        matches!(self, Error::Input { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::YearNotFound => write!(f, "Year not found"),
+            Error::DayNotFound {
+                year,
+                day: Some(day),
+                nearest,
+            } if !nearest.is_empty() => {
+                let nearest: Vec<_> = nearest.iter().map(|d| d.to_string()).collect();
+                write!(
+                    f,
+                    "Day {} not found for {}, nearest registered: {}",
+                    day,
+                    year,
+                    nearest.join(", ")
+                )
+            }
+            Error::DayNotFound { year, .. } => write!(f, "No days registered for {}", year),
+            Error::UnknownParam(name) => write!(f, "unknown parameter `{}`", name),
+            Error::Input { path, source } if source.kind() == io::ErrorKind::NotFound => {
+                write!(f, "no input at {}", path.display())
+            }
+            Error::Input { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Fetch { url, reason } => write!(f, "failed to fetch {}: {}", url, reason),
+            Error::Submit { url, reason } => write!(f, "failed to submit to {}: {}", url, reason),
+            Error::NoSession { path: Some(path) } => write!(
+                f,
+                "no session token, set AOC_SESSION or save it to {}",
+                path.display()
+            ),
+            Error::NoSession { path: None } => write!(f, "no session token, set AOC_SESSION"),
+            Error::Checksum {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} has changed since it was imported (sha256 {}, expected {})",
+                path.display(),
+                actual,
+                expected
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Input { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
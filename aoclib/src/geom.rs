@@ -0,0 +1,375 @@
+//! Points on an integer lattice of any number of dimensions
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A point, or the offset between two points, with `N` integer coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Point([0; N]);
+
+    pub const fn new(coords: [i64; N]) -> Self {
+        Point(coords)
+    }
+
+    pub fn coords(&self) -> [i64; N] {
+        self.0
+    }
+
+    fn map<F: Fn(i64) -> i64>(self, f: F) -> Self {
+        let mut coords = self.0;
+        coords.iter_mut().for_each(|c| *c = f(*c));
+        Point(coords)
+    }
+
+    fn zip_with<F: Fn(i64, i64) -> i64>(self, other: Self, f: F) -> Self {
+        let mut coords = self.0;
+        for (a, &b) in coords.iter_mut().zip(other.0.iter()) {
+            *a = f(*a, b);
+        }
+        Point(coords)
+    }
+
+    /// Distance travelling along one axis at a time
+    pub fn manhattan(&self, other: &Self) -> i64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum()
+    }
+
+    /// Distance travelling along any number of axes at once, like a chess king
+    pub fn chebyshev(&self, other: &Self) -> i64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| (a - b).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The smallest coordinate of either point along each axis
+    pub fn min(self, other: Self) -> Self {
+        self.zip_with(other, i64::min)
+    }
+
+    /// The largest coordinate of either point along each axis
+    pub fn max(self, other: Self) -> Self {
+        self.zip_with(other, i64::max)
+    }
+
+    /// Each coordinate replaced with its sign, giving a step of at most one along each axis
+    pub fn signum(self) -> Self {
+        self.map(i64::signum)
+    }
+
+    /// The `2 * N` points one step away along a single axis
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        (0..N).flat_map(move |axis| {
+            [-1, 1].iter().map(move |d| {
+                let mut neighbor = point;
+                neighbor.0[axis] += d;
+                neighbor
+            })
+        })
+    }
+
+    /// The `3^N - 1` points at most one step away along every axis
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        let count = 3usize.pow(N as u32);
+        // Count each offset in base 3, skipping the middle one where every offset is 0
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut neighbor = point;
+                for c in neighbor.0.iter_mut() {
+                    *c += (i % 3) as i64 - 1;
+                    i /= 3;
+                }
+                neighbor
+            })
+    }
+}
+
+impl Point<2> {
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    /// Rotate a quarter turn anticlockwise about the origin, with y pointing up
+    pub fn rotate_left(self) -> Self {
+        Point([-self.y(), self.x()])
+    }
+
+    /// Rotate a quarter turn clockwise about the origin, with y pointing up
+    pub fn rotate_right(self) -> Self {
+        Point([self.y(), -self.x()])
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Point::ORIGIN
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Point<N> {
+    fn from(coords: [i64; N]) -> Self {
+        Point(coords)
+    }
+}
+
+impl From<(i64, i64)> for Point<2> {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point([x, y])
+    }
+}
+
+impl From<(i64, i64, i64)> for Point<3> {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point([x, y, z])
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|c| -c)
+    }
+}
+
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, scale: i64) -> Self {
+        self.map(|c| c * scale)
+    }
+}
+
+impl<const N: usize> MulAssign<i64> for Point<N> {
+    fn mul_assign(&mut self, scale: i64) {
+        *self = *self * scale;
+    }
+}
+
+/// Written as `(x, y, ...)`
+impl<const N: usize> fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// The smallest box containing some points, including its edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<const N: usize> {
+    pub min: Point<N>,
+    pub max: Point<N>,
+}
+
+impl<const N: usize> BoundingBox<N> {
+    /// The box with `a` and `b` at opposite corners
+    pub fn new(a: Point<N>, b: Point<N>) -> Self {
+        BoundingBox {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// The box around all of `points`, or `None` if there aren't any
+    pub fn from_points<I: IntoIterator<Item = Point<N>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(BoundingBox::new(first, first), BoundingBox::include))
+    }
+
+    /// The box grown just enough to contain `point`
+    pub fn include(self, point: Point<N>) -> Self {
+        BoundingBox {
+            min: self.min.min(point),
+            max: self.max.max(point),
+        }
+    }
+
+    /// The box grown by `by` on every side
+    pub fn grow(self, by: i64) -> Self {
+        let by = Point([by; N]);
+        BoundingBox::new(self.min - by, self.max + by)
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        (0..N).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// The number of points along each axis
+    pub fn size(&self) -> Point<N> {
+        self.max - self.min + Point([1; N])
+    }
+
+    /// The number of points in the box
+    pub fn volume(&self) -> i64 {
+        self.size().0.iter().product()
+    }
+
+    /// Every point in the box, varying the last axis fastest
+    pub fn points(&self) -> impl Iterator<Item = Point<N>> {
+        let BoundingBox { min, max } = *self;
+        let count = self.volume() as usize;
+        (0..count).map(move |mut i| {
+            let mut point = max;
+            for axis in (0..N).rev() {
+                let len = (max[axis] - min[axis] + 1) as usize;
+                point[axis] = min[axis] + (i % len) as i64;
+                i /= len;
+            }
+            point
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point([1, -2, 3]);
+        let b = Point::from((4, 5, -6));
+        assert_eq!(Point([5, 3, -3]), a + b);
+        assert_eq!(Point([-3, -7, 9]), a - b);
+        assert_eq!(Point([-1, 2, -3]), -a);
+        assert_eq!(Point([3, -6, 9]), a * 3);
+        assert_eq!(Point([1, -1, 1]), a.signum());
+        assert_eq!(Point([1, -2, -6]), a.min(b));
+        assert_eq!(Point([4, 5, 3]), a.max(b));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 2;
+        c[0] = 0;
+        assert_eq!(Point([0, 10, -12]), c);
+        assert_eq!("(0, 10, -12)", c.to_string());
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point([1, -2]);
+        assert_eq!(3, a.manhattan(&Point::ORIGIN));
+        assert_eq!(2, a.chebyshev(&Point::ORIGIN));
+        assert_eq!(10, a.manhattan(&Point([-3, 4])));
+        assert_eq!(6, a.chebyshev(&Point([-3, 4])));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let p = Point([3, 1]);
+        assert_eq!(Point([-1, 3]), p.rotate_left());
+        assert_eq!(Point([1, -3]), p.rotate_right());
+        assert_eq!(-p, p.rotate_left().rotate_left());
+        assert_eq!(p, p.rotate_left().rotate_right());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point([5, 5]);
+        assert_eq!(
+            vec![Point([4, 5]), Point([6, 5]), Point([5, 4]), Point([5, 6])],
+            p.orthogonal_neighbors().collect::<Vec<_>>()
+        );
+        assert_eq!(8, p.neighbors().count());
+        assert!(p.neighbors().all(|n| n.chebyshev(&p) == 1));
+        assert_eq!(80, Point::<4>::ORIGIN.neighbors().count());
+        assert_eq!(6, Point::<3>::ORIGIN.orthogonal_neighbors().count());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = vec![Point([1, 5]), Point([-2, 3]), Point([0, 7])];
+        let bounds = BoundingBox::from_points(points).unwrap();
+        assert_eq!(BoundingBox::new(Point([1, 3]), Point([-2, 7])), bounds);
+        assert_eq!(Point([4, 5]), bounds.size());
+        assert_eq!(20, bounds.volume());
+        assert!(bounds.contains(&Point([-2, 7])));
+        assert!(!bounds.contains(&Point([2, 7])));
+        assert_eq!(None, BoundingBox::<2>::from_points(vec![]));
+
+        let bounds = BoundingBox::new(Point::ORIGIN, Point::ORIGIN).grow(1);
+        assert_eq!(9, bounds.points().count());
+        assert_eq!(
+            vec![
+                Point([-1, -1]),
+                Point([-1, 0]),
+                Point([-1, 1]),
+                Point([0, -1])
+            ],
+            bounds.points().take(4).collect::<Vec<_>>()
+        );
+        assert!(bounds
+            .include(Point([3, 3]))
+            .points()
+            .all(|p| p.chebyshev(&Point([1, 1])) <= 2));
+    }
+}
@@ -0,0 +1,601 @@
+//! A rectangular grid of cells, as found in most map and image puzzles
+//!
+//! Positions are `(row, col)` pairs counted from the top left.
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A position within a grid, as `(row, col)`
+pub type Pos = (usize, usize);
+
+/// Steps to the orthogonally adjacent cells: up, left, right and down
+pub const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Steps to the orthogonally and diagonally adjacent cells, in reading order
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// One of the eight ways a grid can be rotated and flipped: mirrored left to right if `flipped`,
+/// then rotated `turns` quarter turns clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub turns: u8,
+}
+
+impl Orientation {
+    /// Every orientation, in the order of [`Grid::orientations`]
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..8).map(|i| Orientation {
+            flipped: i >= 4,
+            turns: i % 4,
+        })
+    }
+}
+
+/// Cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from cells listed row by row.
+    ///
+    /// Panics if the number of cells isn't a multiple of `cols`.
+    pub fn from_vec(cells: Vec<T>, cols: usize) -> Self {
+        let rows = cells.len().checked_div(cols).unwrap_or(0);
+        assert_eq!(
+            cells.len(),
+            rows * cols,
+            "{} cells can't be split into rows of {}",
+            cells.len(),
+            cols
+        );
+        Grid { cells, rows, cols }
+    }
+
+    /// A grid with every cell set to `value`
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// A grid with every cell set to its default
+    pub fn with_size(rows: usize, cols: usize) -> Self
+    where
+        T: Clone + Default,
+    {
+        Grid::filled(rows, cols, T::default())
+    }
+
+    /// Parse a grid with one line per row, converting each character with `f`
+    pub fn parse_with<F, E>(s: &str, mut f: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> Result<T, E>,
+        E: fmt::Display,
+    {
+        let mut cells = Vec::new();
+        let mut cols = None;
+        for (row, line) in s.lines().enumerate() {
+            let start = cells.len();
+            for (col, c) in line.chars().enumerate() {
+                let cell = f(c).map_err(|e| ParseGridError::Cell {
+                    row,
+                    col,
+                    c,
+                    reason: e.to_string(),
+                })?;
+                cells.push(cell);
+            }
+            let len = cells.len() - start;
+            match cols {
+                None => cols = Some(len),
+                Some(cols) if cols != len => {
+                    return Err(ParseGridError::Ragged {
+                        row,
+                        expected: cols,
+                        found: len,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        match cols {
+            Some(cols) if cols > 0 => Ok(Grid::from_vec(cells, cols)),
+            _ => Err(ParseGridError::Empty),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows, cols)`
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.rows && pos.1 < self.cols
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.0 * self.cols + pos.1)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.cells[i])
+    }
+
+    /// The position `delta` away from `pos`, if it's within the grid
+    pub fn step(&self, pos: Pos, delta: (isize, isize)) -> Option<Pos> {
+        let row = pos.0.checked_add_signed(delta.0)?;
+        let col = pos.1.checked_add_signed(delta.1)?;
+        Some((row, col)).filter(|&p| self.contains(p))
+    }
+
+    /// Positions of the cells orthogonally adjacent to `pos`
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// Positions of the cells orthogonally or diagonally adjacent to `pos`
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| (r, c)))
+    }
+
+    /// Every cell along with its position, row by row
+    pub fn enumerate(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Panics if `row` is out of bounds
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} out of bounds", row);
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Panics if `col` is out of bounds
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.rows).map(move |r| self.row(r))
+    }
+
+    pub fn iter_cols(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(move |c| self.col(c))
+    }
+
+    /// A grid of the same size with `f` applied to each cell
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.cells
+    }
+}
+
+/// Transformations, each returning a new grid
+impl<T: Clone> Grid<T> {
+    /// Reflect across the main diagonal, so rows become columns
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.cols).flat_map(|c| self.col(c).cloned()).collect();
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotate90(&self) -> Self {
+        let cells = (0..self.cols)
+            .flat_map(|c| self.col(c).rev().cloned())
+            .collect();
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Rotate a half turn
+    pub fn rotate180(&self) -> Self {
+        Grid {
+            cells: self.cells.iter().rev().cloned().collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Rotate a quarter turn anticlockwise
+    pub fn rotate270(&self) -> Self {
+        let cells = (0..self.cols)
+            .rev()
+            .flat_map(|c| self.col(c).cloned())
+            .collect();
+        Grid {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Mirror left to right, reversing each row
+    pub fn flip_horizontal(&self) -> Self {
+        let cells = self
+            .iter_rows()
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+        Grid {
+            cells,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Mirror top to bottom, reversing the order of the rows
+    pub fn flip_vertical(&self) -> Self {
+        let cells = self
+            .iter_rows()
+            .rev()
+            .flat_map(|row| row.iter().cloned())
+            .collect();
+        Grid {
+            cells,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// The grid turned to `orientation`
+    pub fn orient(&self, orientation: Orientation) -> Self {
+        let grid = if orientation.flipped {
+            self.flip_horizontal()
+        } else {
+            self.clone()
+        };
+        match orientation.turns % 4 {
+            0 => grid,
+            1 => grid.rotate90(),
+            2 => grid.rotate180(),
+            _ => grid.rotate270(),
+        }
+    }
+
+    /// All eight ways the grid can be rotated and flipped: the grid itself and its clockwise
+    /// rotations, then the same for its horizontal mirror image
+    pub fn orientations(&self) -> impl Iterator<Item = Self> + '_ {
+        Orientation::all().map(move |o| self.orient(o))
+    }
+
+    /// The `size` cells with their top left at `pos`, or `None` if they don't all fit in the grid
+    pub fn sub_grid(&self, pos: Pos, size: (usize, usize)) -> Option<Self> {
+        let bottom = pos.0.checked_add(size.0)?;
+        let right = pos.1.checked_add(size.1)?;
+        if bottom > self.rows || right > self.cols {
+            return None;
+        }
+        let cells = (pos.0..bottom)
+            .flat_map(|r| self.row(r)[pos.1..right].iter().cloned())
+            .collect();
+        Some(Grid {
+            cells,
+            rows: size.0,
+            cols: size.1,
+        })
+    }
+
+    /// The grid without the outermost `width` rows and columns on every side. Trimming more than
+    /// half the grid leaves it empty.
+    pub fn trim_border(&self, width: usize) -> Self {
+        let rows = self.rows.saturating_sub(2 * width);
+        let cols = self.cols.saturating_sub(2 * width);
+        if rows == 0 || cols == 0 {
+            return Grid {
+                cells: Vec::new(),
+                rows: 0,
+                cols: 0,
+            };
+        }
+        self.sub_grid((width, width), (rows, cols))
+            .expect("trimmed grid fits within the grid")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} out of bounds for a {:?} grid", pos, self.size()),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let size = self.size();
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} out of bounds for a {:?} grid", pos, size),
+        }
+    }
+}
+
+/// Parses a map with one character per cell, see [`Grid::parse_with`]
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: fmt::Display,
+{
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, T::try_from)
+    }
+}
+
+/// Renders each row on its own line, the reverse of parsing when cells display as one character
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (r, row) in self.iter_rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reasons a grid couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// There were no cells
+    Empty,
+    /// A row's length differed from the first row's
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character couldn't be converted to a cell
+    Cell {
+        row: usize,
+        col: usize,
+        c: char,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "grid has no cells"),
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} columns, expected {}",
+                row, found, expected
+            ),
+            ParseGridError::Cell {
+                row,
+                col,
+                c,
+                reason,
+            } => {
+                write!(f, "invalid cell {:?} at ({}, {}): {}", c, row, col, reason)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseGridError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let g = grid();
+        assert_eq!((2, 3), g.size());
+        assert_eq!('f', g[(1, 2)]);
+        assert_eq!("abc\ndef", g.to_string());
+
+        assert_eq!(Err(ParseGridError::Empty), "".parse::<Grid<char>>());
+        assert_eq!(
+            "row 1 has 2 columns, expected 3",
+            "abc\nde".parse::<Grid<char>>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "invalid cell 'x' at (1, 0): not a wall",
+            Grid::parse_with("#.\nx#", |c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err("not a wall"),
+            })
+            .unwrap_err()
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_access() {
+        let mut g = grid();
+        assert_eq!(Some(&'b'), g.get((0, 1)));
+        assert_eq!(None, g.get((2, 0)));
+        assert_eq!(None, g.get((0, 3)));
+        assert_eq!(None, g.get_mut((0, 3)));
+
+        g[(0, 1)] = 'B';
+        *g.get_mut((1, 1)).unwrap() = 'E';
+        assert_eq!("aBc\ndEf", g.to_string());
+        assert_eq!("ABC\nDEF", g.map(|c| c.to_ascii_uppercase()).to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 3) out of bounds for a (2, 3) grid")]
+    fn test_index_out_of_bounds() {
+        let _ = grid()[(0, 3)];
+    }
+
+    #[test]
+    fn test_rows_and_cols() {
+        let g = grid();
+        assert_eq!(
+            vec![&['a', 'b', 'c'][..], &['d', 'e', 'f']],
+            g.iter_rows().collect::<Vec<_>>()
+        );
+        let cols: Vec<String> = g.iter_cols().map(|c| c.collect()).collect();
+        assert_eq!(vec!["ad", "be", "cf"], cols);
+        assert_eq!("fc", g.col(2).rev().collect::<String>());
+        assert_eq!(
+            vec![((0, 0), &'a'), ((0, 1), &'b')],
+            g.enumerate().take(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_transforms() {
+        let g = grid();
+        assert_eq!("ad\nbe\ncf", g.transpose().to_string());
+        assert_eq!("da\neb\nfc", g.rotate90().to_string());
+        assert_eq!("fed\ncba", g.rotate180().to_string());
+        assert_eq!("cf\nbe\nad", g.rotate270().to_string());
+        assert_eq!("cba\nfed", g.flip_horizontal().to_string());
+        assert_eq!("def\nabc", g.flip_vertical().to_string());
+        assert_eq!(g, g.rotate90().rotate270());
+        assert_eq!(g.rotate180(), g.rotate90().rotate90());
+        assert_eq!(g.transpose(), g.rotate90().flip_horizontal());
+    }
+
+    #[test]
+    fn test_orientations() {
+        let g: Grid<char> = "ab\ncd".parse().unwrap();
+        let all: Vec<String> = g.orientations().map(|o| o.to_string()).collect();
+        assert_eq!(
+            vec!["ab\ncd", "ca\ndb", "dc\nba", "bd\nac", "ba\ndc", "db\nca", "cd\nab", "ac\nbd"],
+            all
+        );
+        assert_eq!(
+            Some(g.transpose()),
+            g.orientations().find(|o| o.row(0) == ['a', 'c'])
+        );
+        assert_eq!(
+            "bd\nac",
+            g.orient(Orientation {
+                flipped: false,
+                turns: 3
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_sub_grid() {
+        let g: Grid<char> = "abcd\nefgh\nijkl\nmnop".parse().unwrap();
+        assert_eq!("fg\njk", g.sub_grid((1, 1), (2, 2)).unwrap().to_string());
+        assert_eq!(
+            "gh\nkl\nop",
+            g.sub_grid((1, 2), (3, 2)).unwrap().to_string()
+        );
+        assert_eq!(None, g.sub_grid((1, 2), (3, 3)));
+        assert_eq!(None, g.sub_grid((4, 0), (1, 1)));
+        assert_eq!(Some(g.clone()), g.sub_grid((0, 0), g.size()));
+
+        assert_eq!("fg\njk", g.trim_border(1).to_string());
+        assert_eq!(g, g.trim_border(0));
+        assert_eq!((0, 0), g.trim_border(2).size());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = Grid::filled(3, 3, 0);
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            g.neighbors4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 1)],
+            g.neighbors8((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(4, g.neighbors4((1, 1)).count());
+        assert_eq!(8, g.neighbors8((1, 1)).count());
+        assert_eq!(
+            vec![(1, 2), (2, 1)],
+            g.neighbors4((2, 2)).collect::<Vec<_>>()
+        );
+        assert_eq!(None, g.step((2, 2), (0, 1)));
+        assert_eq!(Some((0, 2)), g.step((2, 2), (-2, 0)));
+    }
+}
@@ -1,42 +1,336 @@
+pub mod answers;
+pub mod automaton;
+pub mod cache;
+pub mod cli;
 mod day;
+mod error;
+pub mod geom;
+pub mod grid;
+mod options;
+mod params;
+pub mod render;
 mod solver;
+pub mod source;
 pub mod strtools;
+pub mod submit;
+mod timing;
+pub mod turtle;
+mod year;
 
-pub use day::Day;
+use std::path::Path;
 
-pub struct AOC<'a> {
-    days: Vec<Day<'a>>,
+use rayon::prelude::*;
+use std::time::Duration;
+
+pub use cache::input_path;
+pub use day::{Day, DayResult, ParsedDay, PartResult};
+pub use error::Error;
+pub use options::Options;
+pub use params::Params;
+pub use solver::Cancel;
+pub use source::InputSource;
+pub use timing::{Stats, Timing};
+pub use year::{Year, YearResult};
+
+pub struct AOC {
+    years: Vec<Year>,
+    options: Options,
 }
 
-impl<'a> AOC<'a> {
+impl AOC {
     pub fn new() -> Self {
-        AOC { days: Vec::new() }
+        AOC {
+            years: Vec::new(),
+            options: Options::default(),
+        }
+    }
+
+    /// Set how solver parts are timed
+    pub fn timing(mut self, timing: Timing) -> Self {
+        self.options.timing = timing;
+        self
+    }
+
+    /// Give up on any part which takes longer than `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Solve up to `jobs` days at once in [`AOC::run_all`]. Results keep their usual order.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.options.jobs = jobs;
+        self
     }
 
-    pub fn day<'b>(mut self, d: day::Day<'b>) -> Self
-    where
-        'b: 'a,
-    {
-        self.days.push(d);
+    /// Override a parameter declared by the days being solved, see [`Day::param`]
+    pub fn param<V: std::fmt::Display>(mut self, name: &str, value: V) -> Self {
+        self.options.params.insert(name, value);
         self
     }
 
-    pub fn run(self, day: Option<u8>, input: String) -> String {
-        let d = if let Some(day) = day {
-            self.days.into_iter().find(|x| x.day == day)
-        } else {
-            self.days.into_iter().max_by_key(|x| x.day)
-        };
+    /// Solve only `part` of each day. The day's parser still runs if it has one.
+    pub fn part(mut self, part: u8) -> Self {
+        self.options.part = Some(part);
+        self
+    }
 
-        match d {
-            Some(d) => d.solve(input),
-            None => "Error: Day not found".to_string(),
+    pub fn year(mut self, y: Year) -> Self {
+        self.years.push(y);
+        self
+    }
+
+    /// The registered years, in the order they were added
+    pub fn years(&self) -> &[Year] {
+        &self.years
+    }
+
+    /// Solve a single day, defaulting to the latest registered year and day
+    pub fn run(
+        self,
+        year: Option<u16>,
+        day: Option<u8>,
+        input: String,
+    ) -> Result<YearResult, Error> {
+        let y = find_year(self.years, year)?;
+        Ok(YearResult {
+            year: y.year,
+            days: vec![Ok(y.run(day, input, self.options)?)],
+        })
+    }
+
+    /// Solve a single day like [`AOC::run`], fetching its input from `source`
+    pub fn run_from<S: InputSource + ?Sized>(
+        self,
+        year: Option<u16>,
+        day: Option<u8>,
+        source: &S,
+    ) -> Result<YearResult, Error> {
+        let y = find_year(self.years, year)?;
+        Ok(YearResult {
+            year: y.year,
+            days: vec![Ok(y.run_from(day, source, self.options)?)],
+        })
+    }
+
+    /// Solve every registered day of every year (or only of `year`) using inputs found in `dir`
+    pub fn run_all<P: AsRef<Path>>(
+        self,
+        year: Option<u16>,
+        dir: P,
+    ) -> Result<Vec<YearResult>, Error> {
+        let mut years: Vec<_> = self
+            .years
+            .into_iter()
+            .filter(|y| year.is_none_or(|year| y.year == year))
+            .collect();
+        if years.is_empty() {
+            return Err(Error::YearNotFound);
         }
+        years.sort_by_key(|y| y.year);
+        let options = self.options;
+        let dir = dir.as_ref();
+        // An override only has to be declared by a day in one of the years being solved
+        day::check_declared(years.iter().flat_map(|y| y.days()), &options.params)?;
+
+        if options.jobs <= 1 {
+            return Ok(years
+                .into_iter()
+                .map(|y| y.solve_all(dir, options.clone()))
+                .collect());
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(options.jobs)
+            .build()
+            .expect("failed to start solver thread pool");
+        Ok(pool.install(|| {
+            years
+                .into_par_iter()
+                .map(|y| y.solve_all(dir, options.clone()))
+                .collect()
+        }))
     }
 }
 
-impl<'a> Default for AOC<'a> {
+/// The requested year, or the latest one if no year was requested
+fn find_year(years: Vec<Year>, year: Option<u16>) -> Result<Year, Error> {
+    let y = if let Some(year) = year {
+        years.into_iter().find(|x| x.year == year)
+    } else {
+        years.into_iter().max_by_key(|x| x.year)
+    };
+    y.ok_or(Error::YearNotFound)
+}
+
+impl Default for AOC {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::Infallible;
+    use std::fs;
+
+    fn aoc() -> AOC {
+        AOC::new()
+            .year(Year::new(2019).day(Day::new(1).part(1, |_| Ok::<_, Infallible>("2019-1"))))
+            .year(
+                Year::new(2020)
+                    .day(Day::new(1).part(1, |_| Ok::<_, Infallible>("2020-1")))
+                    .day(Day::new(2).part(1, |_| Ok::<_, Infallible>("2020-2"))),
+            )
+    }
+
+    fn answers(result: &YearResult) -> Vec<(u16, u8, u8, String)> {
+        let mut out = Vec::new();
+        for d in result.days.iter() {
+            let d = d.as_ref().unwrap();
+            for p in d.parts.iter() {
+                out.push((
+                    result.year,
+                    d.day,
+                    p.part,
+                    p.answer.as_ref().unwrap().clone(),
+                ));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_run_selects_year_and_day() {
+        let result = aoc().run(Some(2019), Some(1), String::new()).unwrap();
+        assert_eq!(vec![(2019, 1, 1, "2019-1".to_string())], answers(&result));
+
+        let result = aoc().run(Some(2020), Some(1), String::new()).unwrap();
+        assert_eq!(vec![(2020, 1, 1, "2020-1".to_string())], answers(&result));
+    }
+
+    #[test]
+    fn test_run_defaults_to_latest() {
+        let result = aoc().run(None, None, String::new()).unwrap();
+        assert_eq!(vec![(2020, 2, 1, "2020-2".to_string())], answers(&result));
+    }
+
+    #[test]
+    fn test_run_not_found() {
+        assert!(matches!(
+            aoc().run(Some(2018), None, String::new()),
+            Err(Error::YearNotFound)
+        ));
+        let err = aoc().run(Some(2020), Some(9), String::new()).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::DayNotFound { year: 2020, day: Some(9), nearest } if nearest == &[2]
+        ));
+        assert_eq!(
+            "Day 9 not found for 2020, nearest registered: 2",
+            err.to_string()
+        );
+        assert!(matches!(
+            aoc().run(Some(2019), Some(3), String::new()),
+            Err(Error::DayNotFound { nearest, .. }) if nearest == [1]
+        ));
+        assert!(matches!(
+            AOC::new()
+                .year(Year::new(2020))
+                .run(None, None, String::new()),
+            Err(Error::DayNotFound { day: None, .. })
+        ));
+        assert!(matches!(
+            aoc().run_all(Some(2018), "inputs"),
+            Err(Error::YearNotFound)
+        ));
+    }
+
+    /// Inputs held in memory, keyed by year and day
+    struct MemorySource(Vec<(u16, u8, &'static str)>);
+
+    impl InputSource for MemorySource {
+        fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+            self.0
+                .iter()
+                .find(|&&(y, d, _)| (y, d) == (year, day))
+                .map(|&(_, _, input)| input.to_string())
+                .ok_or(Error::Fetch {
+                    url: format!("memory:{}/{}", year, day),
+                    reason: "no input".to_string(),
+                })
+        }
+    }
+
+    #[test]
+    fn test_run_from() {
+        let source = MemorySource(vec![(2020, 2, ""), (2019, 1, "")]);
+
+        let latest = aoc().run_from(None, None, &source).unwrap();
+        assert_eq!(vec![(2020, 2, 1, "2020-2".to_string())], answers(&latest));
+        let y2019 = aoc().run_from(Some(2019), None, &source).unwrap();
+        assert_eq!(vec![(2019, 1, 1, "2019-1".to_string())], answers(&y2019));
+        assert_eq!(
+            "failed to fetch memory:2020/1: no input",
+            aoc()
+                .run_from(Some(2020), Some(1), &source)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_run_from_cache() {
+        let dir = std::env::temp_dir().join(format!("aoclib-run-cached-{}", std::process::id()));
+        cache::import(&dir, 2020, 2, "").unwrap();
+
+        let source = source::CacheSource::new(&dir);
+        let latest = aoc().run_from(None, None, &source);
+        let missing = aoc().run_from(Some(2020), Some(1), &source);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            vec![(2020, 2, 1, "2020-2".to_string())],
+            answers(&latest.unwrap())
+        );
+        assert!(
+            matches!(missing, Err(Error::Input { path, .. }) if path == input_path(&dir, 2020, 1))
+        );
+    }
+
+    #[test]
+    fn test_run_all() {
+        let dir = std::env::temp_dir().join(format!("aoclib-run-all-{}", std::process::id()));
+        fs::create_dir_all(dir.join("2020")).unwrap();
+        fs::write(input_path(&dir, 2020, 2), "").unwrap();
+
+        let results = aoc().run_all(Some(2020), &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let expected = format!(
+            "Year Day Part  Answer\n\
+             2020   1    -  skipped: no input at {}\n\
+             2020   2    1  2020-2\n",
+            input_path(&dir, 2020, 1).display()
+        );
+        assert_eq!(expected, render::table(&results, false));
+    }
+
+    #[test]
+    fn test_run_all_parallel_keeps_order() {
+        let dir = std::env::temp_dir().join(format!("aoclib-run-all-par-{}", std::process::id()));
+        fs::create_dir_all(dir.join("2020")).unwrap();
+        let mut year = Year::new(2020);
+        for day in (1..=25).rev() {
+            fs::write(input_path(&dir, 2020, day), day.to_string()).unwrap();
+            year = year.day(Day::new(day).part(1, |input: String| input.parse::<u8>()));
+        }
+
+        let results = AOC::new().year(year).jobs(4).run_all(None, &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let expected: Vec<_> = (1..=25).map(|d| (2020, d, 1, d.to_string())).collect();
+        assert_eq!(expected, answers(&results[0]));
+    }
+}
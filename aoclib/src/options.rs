@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use super::params::Params;
+use super::timing::Timing;
+
+/// Settings controlling how days and their parts are run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    pub timing: Timing,
+    /// Give up on a part which hasn't finished within this long
+    pub timeout: Option<Duration>,
+    /// Number of days solved at once when running every day. 0 or 1 solves them in sequence.
+    pub jobs: usize,
+    /// Overrides for the parameters declared by each day
+    pub params: Params,
+    /// Solve only this part of each day, rather than all of them
+    pub part: Option<u8>,
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+/// Named values which tune how a solver runs, such as the number of rounds to play.
+///
+/// Days declare each parameter with the value the real puzzle uses (see [`Day::param`]) and the
+/// command line can override it, e.g. to run a full solver against a smaller example.
+///
+/// [`Day::param`]: crate::Day::param
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Params(BTreeMap::new())
+    }
+
+    /// Set `name` to `value`, replacing any previous value
+    pub fn set<V: fmt::Display>(mut self, name: &str, value: V) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert<V: fmt::Display>(&mut self, name: &str, value: V) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    /// Look up `name` and parse it as a `T`
+    pub fn get<T>(&self, name: &str) -> Result<T, anyhow::Error>
+    where
+        T: FromStr,
+        T::Err: error::Error + Send + Sync + 'static,
+    {
+        let value = self
+            .0
+            .get(name)
+            .ok_or_else(|| anyhow!("missing parameter `{}`", name))?;
+        value
+            .parse()
+            .map_err(|e| anyhow!("invalid value {:?} for parameter `{}`: {}", value, name, e))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let params = Params::new().set("preamble", 5).set("name", "abc");
+
+        assert_eq!(5, params.get::<usize>("preamble").unwrap());
+        assert_eq!("abc", params.get::<String>("name").unwrap());
+        assert_eq!(
+            "missing parameter `turns`",
+            params.get::<usize>("turns").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "invalid value \"abc\" for parameter `name`: invalid digit found in string",
+            params.get::<usize>("name").unwrap_err().to_string()
+        );
+    }
+}
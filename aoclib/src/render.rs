@@ -0,0 +1,429 @@
+//! Renderers that turn solver results into text for display or for other tools to ingest
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+
+use super::answers::Verdict;
+use super::day::{DayResult, PartResult};
+use super::timing::Stats;
+use super::year::{Year, YearResult};
+
+/// Output formats understood by the runner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format {:?}", s)),
+        }
+    }
+}
+
+/// Render a table of every registered part of `years`, sorted by year and day, along with the
+/// default value of each of its parameters
+pub fn list<'a, I: IntoIterator<Item = &'a Year>>(years: I) -> String {
+    let mut years: Vec<_> = years.into_iter().collect();
+    years.sort_by_key(|y| y.year);
+
+    let mut out = format!("{:>4} {:>3} {:>4}  {}\n", "Year", "Day", "Part", "Params");
+    for y in years {
+        let mut days: Vec<_> = y.days().iter().collect();
+        days.sort_by_key(|d| d.day);
+        for d in days {
+            for part in d.parts() {
+                let params: Vec<_> = d
+                    .params(part)
+                    .into_iter()
+                    .flat_map(|p| p.iter())
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect();
+                let row = format!(
+                    "{:>4} {:>3} {:>4}  {}",
+                    y.year,
+                    d.day,
+                    part,
+                    params.join(" ")
+                );
+                writeln!(out, "{}", row.trim_end()).unwrap();
+            }
+        }
+    }
+    out
+}
+
+/// Render each day as "Part: N" / "Solution: X" lines
+///
+/// When `time` is set, the duration of each part is included. Benchmark statistics are always
+/// included when present. Failed parts and skipped days only get a short status, since the reasons
+/// are reported on stderr.
+pub fn text(results: &[YearResult], time: bool) -> String {
+    let mut out = String::new();
+    for d in results.iter().flat_map(|y| y.days.iter()) {
+        match d {
+            Ok(d) => text_day(&mut out, d, time),
+            Err((_, e)) => writeln!(out, "Skipped: {}", e).unwrap(),
+        }
+    }
+    out
+}
+
+fn text_day(out: &mut String, result: &DayResult, time: bool) {
+    if let (Some(parse), true) = (result.parse, time) {
+        writeln!(out, "Parse: {:?}", parse).unwrap();
+    }
+    for part in result.parts.iter() {
+        writeln!(out, "Part: {}", part.part).unwrap();
+        match &part.answer {
+            Ok(solution) => writeln!(out, "Solution: {}", solution).unwrap(),
+            Err(_) => writeln!(out, "Status: failed").unwrap(),
+        }
+        if let Some(stats) = &part.bench {
+            writeln!(out, "Bench: {}", stats).unwrap();
+        } else if time {
+            writeln!(out, "Time: {:?}", part.duration).unwrap();
+        }
+        if let Some(verdict) = &part.verdict {
+            writeln!(out, "Check: {}", verdict).unwrap();
+        }
+    }
+}
+
+/// Render the results of several years as a table with one row per part
+pub fn table(results: &[YearResult], time: bool) -> String {
+    let mut out = format!("{:>4} {:>3} {:>4}  {}\n", "Year", "Day", "Part", "Answer");
+    for y in results {
+        for d in y.days.iter() {
+            match d {
+                Ok(d) => {
+                    if let (Some(parse), true) = (d.parse, time) {
+                        writeln!(
+                            out,
+                            "{:>4} {:>3} {:>4}  ({:?})",
+                            y.year, d.day, "parse", parse
+                        )
+                        .unwrap();
+                    }
+                    for part in d.parts.iter() {
+                        table_row(&mut out, y.year, d.day, part, time);
+                    }
+                }
+                Err((day, e)) => {
+                    writeln!(out, "{:>4} {:>3} {:>4}  skipped: {}", y.year, day, "-", e).unwrap()
+                }
+            }
+        }
+    }
+    out
+}
+
+fn table_row(out: &mut String, year: u16, day: u8, part: &PartResult, time: bool) {
+    write!(out, "{:>4} {:>3} {:>4}  ", year, day, part.part).unwrap();
+    if let Some(verdict) = &part.verdict {
+        write!(out, "[{}] ", verdict).unwrap();
+    }
+    match &part.answer {
+        Ok(solution) => write!(out, "{}", solution),
+        Err(_) => write!(out, "failed"),
+    }
+    .unwrap();
+    if let Some(stats) = &part.bench {
+        write!(out, " ({})", stats).unwrap();
+    } else if time {
+        write!(out, " ({:?})", part.duration).unwrap();
+    }
+    writeln!(out).unwrap();
+}
+
+/// Render the results as a JSON array of years, each holding its days and their parts
+///
+/// Durations are in nanoseconds. A day which couldn't be solved has an `error` instead of `parts`.
+pub fn json(results: &[YearResult]) -> String {
+    let years: Vec<Value> = results
+        .iter()
+        .map(|y| {
+            let days: Vec<Value> = y
+                .days
+                .iter()
+                .map(|d| match d {
+                    Ok(d) => json!({
+                        "day": d.day,
+                        "parse_ns": d.parse.map(|p| p.as_nanos() as u64),
+                        "parts": d.parts.iter().map(json_part).collect::<Vec<_>>(),
+                    }),
+                    Err((day, e)) => json!({ "day": day, "error": e.to_string() }),
+                })
+                .collect();
+            json!({ "year": y.year, "days": days })
+        })
+        .collect();
+    let mut out = serde_json::to_string_pretty(&years).unwrap();
+    out.push('\n');
+    out
+}
+
+fn json_part(part: &PartResult) -> Value {
+    let (answer, error) = match &part.answer {
+        Ok(answer) => (Some(answer.clone()), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    json!({
+        "part": part.part,
+        "answer": answer,
+        "error": error,
+        "duration_ns": part.duration.as_nanos() as u64,
+        "bench": part.bench.as_ref().map(json_stats),
+        "verdict": part.verdict.as_ref().map(json_verdict),
+    })
+}
+
+fn json_verdict(verdict: &Verdict) -> Value {
+    match verdict {
+        Verdict::Pass => json!({ "status": "pass" }),
+        Verdict::Fail { expected } => json!({ "status": "fail", "expected": expected }),
+        Verdict::New => json!({ "status": "new" }),
+        Verdict::InputMismatch { expected } => {
+            json!({ "status": "input_mismatch", "expected": expected })
+        }
+    }
+}
+
+fn json_stats(stats: &Stats) -> Value {
+    json!({
+        "runs": stats.runs,
+        "min_ns": stats.min.as_nanos() as u64,
+        "median_ns": stats.median.as_nanos() as u64,
+        "mean_ns": stats.mean.as_nanos() as u64,
+    })
+}
+
+/// Render the results as CSV with one row per part
+///
+/// Durations are in nanoseconds. The benchmark columns are empty unless benchmarking. Days with a
+/// shared parser have an extra row with `parse` as the part, and a day which couldn't be solved is
+/// a single row with an empty part and the reason in `error`.
+pub fn csv(results: &[YearResult]) -> String {
+    let mut out = String::from(
+        "year,day,part,answer,error,duration_ns,runs,min_ns,median_ns,mean_ns,verdict,expected\n",
+    );
+    for y in results {
+        for d in y.days.iter() {
+            match d {
+                Ok(d) => {
+                    if let Some(parse) = d.parse {
+                        writeln!(
+                            out,
+                            "{},{},parse,,,{},,,,,,",
+                            y.year,
+                            d.day,
+                            parse.as_nanos()
+                        )
+                        .unwrap();
+                    }
+                    for part in d.parts.iter() {
+                        let (answer, error) = match &part.answer {
+                            Ok(answer) => (answer.clone(), String::new()),
+                            Err(e) => (String::new(), e.to_string()),
+                        };
+                        let bench = match &part.bench {
+                            Some(s) => format!(
+                                "{},{},{},{}",
+                                s.runs,
+                                s.min.as_nanos(),
+                                s.median.as_nanos(),
+                                s.mean.as_nanos()
+                            ),
+                            None => ",,,".to_string(),
+                        };
+                        let verdict = match &part.verdict {
+                            Some(Verdict::Pass) => "pass,".to_string(),
+                            Some(Verdict::Fail { expected }) => {
+                                format!("fail,{}", csv_field(expected))
+                            }
+                            Some(Verdict::New) => "new,".to_string(),
+                            Some(Verdict::InputMismatch { expected }) => {
+                                format!("input_mismatch,{}", expected)
+                            }
+                            None => ",".to_string(),
+                        };
+                        writeln!(
+                            out,
+                            "{},{},{},{},{},{},{},{}",
+                            y.year,
+                            d.day,
+                            part.part,
+                            csv_field(&answer),
+                            csv_field(&error),
+                            part.duration.as_nanos(),
+                            bench,
+                            verdict
+                        )
+                        .unwrap();
+                    }
+                }
+                Err((day, e)) => writeln!(
+                    out,
+                    "{},{},,,{},,,,,,,",
+                    y.year,
+                    day,
+                    csv_field(&e.to_string())
+                )
+                .unwrap(),
+            }
+        }
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+    use std::io;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn results() -> Vec<YearResult> {
+        vec![YearResult {
+            year: 2020,
+            days: vec![
+                Ok(DayResult {
+                    day: 1,
+                    checksum: String::new(),
+                    parse: Some(Duration::from_nanos(300)),
+                    parts: vec![
+                        PartResult {
+                            part: 1,
+                            answer: Ok("42".to_string()),
+                            duration: Duration::from_nanos(1500),
+                            bench: None,
+                            verdict: Some(Verdict::Pass),
+                        },
+                        PartResult {
+                            part: 2,
+                            answer: Err(anyhow::anyhow!("bad \"input\", sorry")),
+                            duration: Duration::from_nanos(20),
+                            bench: None,
+                            verdict: None,
+                        },
+                    ],
+                }),
+                Err((
+                    2,
+                    Error::Input {
+                        path: PathBuf::from("day02.txt"),
+                        source: io::ErrorKind::NotFound.into(),
+                    },
+                )),
+            ],
+        }]
+    }
+
+    #[test]
+    fn test_list() {
+        let solver = |_: &str, _: &crate::Params| Ok::<_, std::convert::Infallible>(0);
+        let years = vec![
+            Year::new(2020)
+                .day(
+                    crate::Day::new(9)
+                        .part_with_params(1, solver)
+                        .param(1, "preamble", 25),
+                )
+                .day(
+                    crate::Day::new(1)
+                        .part_str(1, str::parse::<u32>)
+                        .part_str(2, str::parse::<u32>),
+                ),
+            Year::new(2019).day(crate::Day::new(1).part_str(1, str::parse::<u32>)),
+        ];
+
+        assert_eq!(
+            "Year Day Part  Params
+2019   1    1
+2020   1    1
+2020   1    2
+2020   9    1  preamble=25
+",
+            list(&years)
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            "Parse: 300ns\n\
+             Part: 1\nSolution: 42\nTime: 1.5µs\nCheck: PASS\n\
+             Part: 2\nStatus: failed\nTime: 20ns\n\
+             Skipped: no input at day02.txt\n",
+            text(&results(), true)
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let v: Value = serde_json::from_str(&json(&results())).unwrap();
+        assert_eq!(
+            json!([{
+                "year": 2020,
+                "days": [
+                    {
+                        "day": 1,
+                        "parse_ns": 300,
+                        "parts": [
+                            {
+                                "part": 1,
+                                "answer": "42",
+                                "error": null,
+                                "duration_ns": 1500,
+                                "bench": null,
+                                "verdict": { "status": "pass" },
+                            },
+                            {
+                                "part": 2,
+                                "answer": null,
+                                "error": "bad \"input\", sorry",
+                                "duration_ns": 20,
+                                "bench": null,
+                                "verdict": null,
+                            },
+                        ],
+                    },
+                    { "day": 2, "error": "no input at day02.txt" },
+                ],
+            }]),
+            v
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "year,day,part,answer,error,duration_ns,runs,min_ns,median_ns,mean_ns,verdict,expected\n\
+             2020,1,parse,,,300,,,,,,\n\
+             2020,1,1,42,,1500,,,,,pass,\n\
+             2020,1,2,,\"bad \"\"input\"\", sorry\",20,,,,,,\n\
+             2020,2,,,no input at day02.txt,,,,,,,\n",
+            csv(&results())
+        );
+    }
+}
@@ -1,15 +1,156 @@
+use std::any::Any;
+use std::borrow::Borrow;
 use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-pub trait Solver {
-    fn solve(self: Box<Self>, input: String) -> Result<String, anyhow::Error>;
+use anyhow::anyhow;
+
+use super::params::Params;
+
+/// Puzzle input shared between the parts of a day
+#[derive(Clone)]
+pub struct Input {
+    pub raw: Arc<str>,
+    /// Output of the day's shared parser, if it has one
+    pub parsed: Option<Arc<dyn Any + Send + Sync>>,
+    /// Parameters of the part being solved
+    pub params: Params,
+    /// Set when the part runs out of time
+    pub cancel: Cancel,
+}
+
+/// A flag telling a solver to give up, set when its part times out.
+///
+/// A timed-out solver can't be stopped from the outside, so long-running loops should poll this
+/// and bail out rather than carrying on in the background.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Cancel::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail with an error once cancelled, for use with `?` inside a loop
+    pub fn check(&self) -> Result<(), anyhow::Error> {
+        if self.is_cancelled() {
+            Err(anyhow!("cancelled"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Solvers are run on worker threads when a timeout is set, so they must be shareable
+pub trait Solver: Send + Sync {
+    fn solve(&self, input: &Input) -> Result<String, anyhow::Error>;
 }
 
+/// A type-erased parser whose output is handed to every part of a day
+pub type Parser = dyn Fn(&str) -> Result<Arc<dyn Any + Send + Sync>, anyhow::Error> + Send + Sync;
+
+/// Solvers taking ownership of the input are given their own copy of it
 impl<F, T: fmt::Display, E: Into<anyhow::Error>> Solver for F
 where
-    F: Fn(String) -> Result<T, E>,
+    F: Fn(String) -> Result<T, E> + Send + Sync,
+{
+    fn solve(&self, input: &Input) -> Result<String, anyhow::Error> {
+        match self(input.raw.to_string()) {
+            Ok(v) => Ok(v.to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Adapter for solvers which only borrow the input, so it can be shared between parts.
+///
+/// This can't be a second blanket impl since a closure could implement both `Fn` signatures.
+pub struct Borrowed<F>(pub F);
+
+impl<F, T: fmt::Display, E: Into<anyhow::Error>> Solver for Borrowed<F>
+where
+    F: Fn(&str) -> Result<T, E> + Send + Sync,
+{
+    fn solve(&self, input: &Input) -> Result<String, anyhow::Error> {
+        match (self.0)(&input.raw) {
+            Ok(v) => Ok(v.to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Adapter for solvers which borrow the input and also take the part's parameters
+pub struct WithParams<F>(pub F);
+
+impl<F, T: fmt::Display, E: Into<anyhow::Error>> Solver for WithParams<F>
+where
+    F: Fn(&str, &Params) -> Result<T, E> + Send + Sync,
+{
+    fn solve(&self, input: &Input) -> Result<String, anyhow::Error> {
+        match (self.0)(&input.raw, &input.params) {
+            Ok(v) => Ok(v.to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Adapter for solvers which borrow the input and poll a [`Cancel`] flag so they can stop early
+/// when they time out
+pub struct Cancellable<F>(pub F);
+
+impl<F, T: fmt::Display, E: Into<anyhow::Error>> Solver for Cancellable<F>
+where
+    F: Fn(&str, &Cancel) -> Result<T, E> + Send + Sync,
+{
+    fn solve(&self, input: &Input) -> Result<String, anyhow::Error> {
+        match (self.0)(&input.raw, &input.cancel) {
+            Ok(v) => Ok(v.to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Adapter for solvers which take the output of the day's parser, or anything it can be borrowed
+/// as (e.g. `&[u64]` for a `Vec<u64>`), along with the part's parameters
+pub struct Parsed<P, B: ?Sized, F> {
+    f: F,
+    _marker: PhantomData<fn(&P, &B)>,
+}
+
+impl<P, B: ?Sized, F> Parsed<P, B, F> {
+    pub fn new(f: F) -> Self {
+        Parsed {
+            f,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P, B, F, T, E> Solver for Parsed<P, B, F>
+where
+    P: Borrow<B> + 'static,
+    B: ?Sized,
+    F: Fn(&B, &Params) -> Result<T, E> + Send + Sync,
+    T: fmt::Display,
+    E: Into<anyhow::Error>,
 {
-    fn solve(self: Box<Self>, input: String) -> Result<String, anyhow::Error> {
-        match self(input) {
+    fn solve(&self, input: &Input) -> Result<String, anyhow::Error> {
+        let parsed = input
+            .parsed
+            .as_ref()
+            .and_then(|p| p.downcast_ref::<P>())
+            .ok_or_else(|| anyhow!("input was not parsed"))?;
+        match (self.f)(parsed.borrow(), &input.params) {
             Ok(v) => Ok(v.to_string()),
             Err(e) => Err(e.into()),
         }
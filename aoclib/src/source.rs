@@ -0,0 +1,219 @@
+//! Places puzzle inputs can be fetched from
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use super::cache;
+use super::error::Error;
+
+/// Somewhere the input for a given year and day can be fetched from
+pub trait InputSource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error>;
+}
+
+/// A single file, or stdin if its path is "-". The same input is returned for every day.
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileSource { path: path.into() }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        if self.path == Path::new("-") {
+            let mut data = String::new();
+            return io::stdin().read_to_string(&mut data).map(move |_| data);
+        }
+        fs::read_to_string(&self.path)
+    }
+}
+
+impl InputSource for FileSource {
+    fn fetch(&self, _year: u16, _day: u8) -> Result<String, Error> {
+        self.read().map_err(|source| Error::Input {
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
+
+/// The local input cache, see [`cache`]
+pub struct CacheSource {
+    dir: PathBuf,
+}
+
+impl CacheSource {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        CacheSource { dir: dir.into() }
+    }
+}
+
+impl InputSource for CacheSource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        cache::read(&self.dir, year, day)
+    }
+}
+
+/// Environment variable holding the session token used to download inputs
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Where inputs are downloaded from unless told otherwise
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request so the site knows what's making them
+pub(crate) const USER_AGENT: &str = "advent-of-code-rs aoclib";
+
+/// Downloads inputs from the Advent of Code site, logged in with a session token
+pub struct HttpSource {
+    url: String,
+    session: String,
+}
+
+impl HttpSource {
+    pub fn new<S: Into<String>>(session: S) -> Self {
+        HttpSource {
+            url: DEFAULT_URL.to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Download from a site other than [`DEFAULT_URL`]
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Use the configured session token, see [`session`]
+    pub fn from_config() -> Result<Self, Error> {
+        session().map(HttpSource::new)
+    }
+}
+
+impl InputSource for HttpSource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.url.trim_end_matches('/'),
+            year,
+            day
+        );
+        let fail = |reason: String| Error::Fetch {
+            url: url.clone(),
+            reason,
+        };
+
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(code, response) => {
+                    fail(format!("{} {}", code, response.status_text()))
+                }
+                e => fail(e.to_string()),
+            })?;
+        response.into_string().map_err(|e| fail(e.to_string()))
+    }
+}
+
+/// The session token from [`SESSION_VAR`], or else the one saved in [`session_path`]
+pub fn session() -> Result<String, Error> {
+    if let Some(session) = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty()) {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = session_path();
+    match path.as_ref().map(fs::read_to_string) {
+        Some(Ok(session)) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        Some(Err(source)) if source.kind() != io::ErrorKind::NotFound => Err(Error::Input {
+            path: path.unwrap(),
+            source,
+        }),
+        _ => Err(Error::NoSession { path }),
+    }
+}
+
+/// The file a session token is saved in: `$XDG_CONFIG_HOME/aoc/session`, falling back to
+/// `$HOME/.config/aoc/session`
+pub fn session_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|config| config.join("aoc").join("session"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve a single request with `status` and `body`, returning the request's head
+    fn stub_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_http_fetch() {
+        let (url, server) = stub_server("200 OK", "1\n2\n3\n");
+
+        let input = HttpSource::new("abc123").url(url).fetch(2020, 5).unwrap();
+        let head = server.join().unwrap().to_lowercase();
+
+        assert_eq!("1\n2\n3\n", input);
+        assert!(
+            head.starts_with("get /2020/day/5/input http/1.1"),
+            "{}",
+            head
+        );
+        assert!(head.contains("cookie: session=abc123\r\n"), "{}", head);
+    }
+
+    #[test]
+    fn test_http_fetch_error() {
+        let (url, server) = stub_server("404 Not Found", "not unlocked yet");
+
+        let err = HttpSource::new("abc123")
+            .url(&url)
+            .fetch(2020, 25)
+            .unwrap_err();
+        server.join().unwrap();
+
+        assert_eq!(
+            format!("failed to fetch {}/2020/day/25/input: 404 Not Found", url),
+            err.to_string()
+        );
+    }
+}
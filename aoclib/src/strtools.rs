@@ -1,6 +1,5 @@
-
 /// Split a str into exactly two substrings. If the delimeter isn't found in the string, (s, "") is returned
-pub fn split_once<'a, 'b>(s: &'a str, delimeter: &'b str) -> (&'a str, &'a str) {
+pub fn split_once<'a>(s: &'a str, delimeter: &str) -> (&'a str, &'a str) {
     if let Some(index) = s.find(delimeter) {
         let (head, tail) = s.split_at(index);
         let (_, tail) = tail.split_at(delimeter.len());
@@ -21,4 +20,4 @@ mod test {
         assert_eq!(("a", "b-c"), split_once("a-b-c", "-"));
         assert_eq!(("a", "-b"), split_once("a--b", "-"));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,484 @@
+//! Submitting answers and remembering how they were judged
+//!
+//! Accepted answers are added to the answers used by `--verify` (see [`answers`]). Rejected ones
+//! are kept in `<dir>/<year>.submissions.toml` so they're never submitted twice:
+//!
+//! ```toml
+//! [7.2]
+//! wrong = ["1234"]
+//! too_high = ["99999"]
+//! too_low = ["12"]
+//! ```
+//!
+//! [`answers`]: crate::answers
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+
+use super::answers::Answers;
+use super::error::Error;
+use super::source::{self, DEFAULT_URL, USER_AGENT};
+
+/// How the site judged a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The previous answer was submitted too recently. Holds the site's explanation, which says how
+    /// long is left to wait.
+    TooSoon(String),
+    /// The part has already been solved or isn't unlocked yet
+    WrongLevel,
+    /// A response that wasn't understood, holding its text
+    Unknown(String),
+}
+
+impl Response {
+    /// Work out the response from the text of the page returned after submitting
+    pub fn parse(page: &str) -> Self {
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Response::Correct
+        } else if text.contains("too high") {
+            Response::TooHigh
+        } else if text.contains("too low") {
+            Response::TooLow
+        } else if text.contains("not the right answer") {
+            Response::Wrong
+        } else if text.contains("answer too recently") {
+            Response::TooSoon(text)
+        } else if text.contains("solving the right level") {
+            Response::WrongLevel
+        } else {
+            Response::Unknown(text)
+        }
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Correct => write!(f, "correct"),
+            Response::Wrong => write!(f, "wrong"),
+            Response::TooHigh => write!(f, "wrong, too high"),
+            Response::TooLow => write!(f, "wrong, too low"),
+            Response::TooSoon(text) => write!(f, "too soon: {}", text),
+            Response::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Response::Unknown(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, or of the whole page if it has none, without any markup
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Somewhere answers can be submitted to
+pub trait Submitter {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Response, Error>;
+}
+
+/// Submits answers to the Advent of Code site, logged in with a session token
+pub struct HttpSubmitter {
+    url: String,
+    session: String,
+}
+
+impl HttpSubmitter {
+    pub fn new<S: Into<String>>(session: S) -> Self {
+        HttpSubmitter {
+            url: DEFAULT_URL.to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Submit to a site other than [`DEFAULT_URL`]
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Use the configured session token, see [`source::session`]
+    pub fn from_config() -> Result<Self, Error> {
+        source::session().map(HttpSubmitter::new)
+    }
+}
+
+impl Submitter for HttpSubmitter {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Response, Error> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.url.trim_end_matches('/'),
+            year,
+            day
+        );
+        let fail = |reason: String| Error::Submit {
+            url: url.clone(),
+            reason,
+        };
+
+        let response = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| match e {
+                ureq::Error::Status(code, response) => {
+                    fail(format!("{} {}", code, response.status_text()))
+                }
+                e => fail(e.to_string()),
+            })?;
+        let page = response.into_string().map_err(|e| fail(e.to_string()))?;
+        Ok(Response::parse(&page))
+    }
+}
+
+/// Answers to a single part which were rejected
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Rejected {
+    wrong: Vec<String>,
+    too_high: Vec<String>,
+    too_low: Vec<String>,
+}
+
+/// Rejected answers for a single year, keyed by day and part
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Submissions(HashMap<(u8, u8), Rejected>);
+
+impl Submissions {
+    /// Location of the submissions for `year` within an answers directory
+    pub fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{}.submissions.toml", year))
+    }
+
+    /// Load the submissions for `year`. A missing file means nothing has been rejected yet.
+    pub fn load(dir: &Path, year: u16) -> Result<Self, anyhow::Error> {
+        let path = Submissions::path(dir, year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Submissions::parse(&s).with_context(|| format!("invalid {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, anyhow::Error> {
+        let mut submissions = HashMap::new();
+        let days = match s.parse::<toml::Value>()? {
+            toml::Value::Table(days) => days,
+            _ => bail!("expected a table of days"),
+        };
+
+        for (day, parts) in days {
+            let day: u8 = day
+                .parse()
+                .with_context(|| format!("invalid day {:?}", day))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| anyhow!("day {} must be a table of parts", day))?;
+            for (part, rejected) in parts {
+                let part: u8 = part
+                    .parse()
+                    .with_context(|| format!("invalid part {:?} on day {}", part, day))?;
+                let list = |name: &str| -> Result<Vec<String>, anyhow::Error> {
+                    match rejected.get(name) {
+                        None => Ok(Vec::new()),
+                        Some(toml::Value::Array(answers)) => answers
+                            .iter()
+                            .map(|a| match a {
+                                toml::Value::String(s) => Ok(s.clone()),
+                                _ => bail!("day {} part {}: {} must hold strings", day, part, name),
+                            })
+                            .collect(),
+                        Some(_) => bail!("day {} part {}: {} must be an array", day, part, name),
+                    }
+                };
+                let rejected = Rejected {
+                    wrong: list("wrong")?,
+                    too_high: list("too_high")?,
+                    too_low: list("too_low")?,
+                };
+                submissions.insert((day, part), rejected);
+            }
+        }
+        Ok(Submissions(submissions))
+    }
+
+    pub fn save(&self, dir: &Path, year: u16) -> Result<(), anyhow::Error> {
+        let mut days = toml::value::Table::new();
+        for (&(day, part), rejected) in self.0.iter() {
+            let mut lists = toml::value::Table::new();
+            for (name, answers) in [
+                ("wrong", &rejected.wrong),
+                ("too_high", &rejected.too_high),
+                ("too_low", &rejected.too_low),
+            ] {
+                if !answers.is_empty() {
+                    let answers = answers.iter().cloned().map(toml::Value::String).collect();
+                    lists.insert(name.to_string(), toml::Value::Array(answers));
+                }
+            }
+            days.entry(day.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+                .as_table_mut()
+                .unwrap()
+                .insert(part.to_string(), toml::Value::Table(lists));
+        }
+
+        let path = Submissions::path(dir, year);
+        let contents = toml::to_string(&toml::Value::Table(days))?;
+        fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&path, contents))
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Remember that `answer` was rejected with `response`. Other responses are ignored.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, response: &Response) {
+        let rejected = self.0.entry((day, part)).or_default();
+        let list = match response {
+            Response::Wrong => &mut rejected.wrong,
+            Response::TooHigh => &mut rejected.too_high,
+            Response::TooLow => &mut rejected.too_low,
+            _ => return,
+        };
+        if !list.iter().any(|a| a == answer) {
+            list.push(answer.to_string());
+        }
+    }
+
+    /// Explain why `answer` is known to be wrong, if it is. Besides answers rejected before, this
+    /// covers numbers beyond one already rejected as too high or too low.
+    pub fn known_wrong(&self, day: u8, part: u8, answer: &str) -> Option<String> {
+        let rejected = self.0.get(&(day, part))?;
+        if rejected.wrong.iter().any(|a| a == answer) {
+            return Some(format!("{} was already rejected", answer));
+        }
+
+        if let Some(bound) = passed_bound(&rejected.too_high, answer, |n, b| n > b) {
+            return Some(format!("{} is too high, {} already was", answer, bound));
+        }
+        if let Some(bound) = passed_bound(&rejected.too_low, answer, |n, b| n < b) {
+            return Some(format!("{} is too low, {} already was", answer, bound));
+        }
+        None
+    }
+}
+
+/// The first of `bounds` which `answer` is equal to, or is numerically beyond according to `beyond`
+fn passed_bound<'a>(
+    bounds: &'a [String],
+    answer: &str,
+    beyond: fn(i128, i128) -> bool,
+) -> Option<&'a str> {
+    let num = answer.parse::<i128>().ok();
+    bounds
+        .iter()
+        .find(|b| *b == answer || matches!((num, b.parse()), (Some(n), Ok(b)) if beyond(n, b)))
+        .map(String::as_str)
+}
+
+/// What happened to an answer given to [`submit`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer was submitted and judged
+    Sent(Response),
+    /// Not submitted since the part already has an accepted answer, which is held
+    AlreadyAccepted(String),
+    /// Not submitted since it's known to be wrong, for the reason held
+    KnownWrong(String),
+    /// Not submitted since the day's other answers were found with a different input, whose
+    /// checksum is held
+    InputMismatch(String),
+}
+
+/// Submit `answer` unless it's already known to be right or wrong, and record the response in
+/// `dir`. Accepted answers are recorded along with `checksum`, the checksum of the input they were
+/// found with, so answers found with a different input than the day's recorded ones aren't sent.
+pub fn submit<S: Submitter + ?Sized>(
+    submitter: &S,
+    dir: &Path,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+    checksum: &str,
+) -> Result<Outcome, anyhow::Error> {
+    let answers = Answers::load(dir, year)?;
+    if let Some(accepted) = answers.get(day, part) {
+        return Ok(Outcome::AlreadyAccepted(accepted.to_string()));
+    }
+    if let Some(recorded) = answers.checksum(day).filter(|&sum| sum != checksum) {
+        return Ok(Outcome::InputMismatch(recorded.to_string()));
+    }
+    let mut submissions = Submissions::load(dir, year)?;
+    if let Some(reason) = submissions.known_wrong(day, part, answer) {
+        return Ok(Outcome::KnownWrong(reason));
+    }
+
+    let response = submitter.submit(year, day, part, answer)?;
+    match response {
+        Response::Correct => Answers::record(dir, year, day, part, answer, checksum)?,
+        Response::Wrong | Response::TooHigh | Response::TooLow => {
+            submissions.record(day, part, answer, &response);
+            submissions.save(dir, year)?;
+        }
+        _ => {}
+    }
+    Ok(Outcome::Sent(response))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Judges answers against a known one, remembering everything submitted
+    struct FakeSubmitter {
+        answer: i64,
+        submitted: RefCell<Vec<String>>,
+    }
+
+    impl Submitter for FakeSubmitter {
+        fn submit(&self, _year: u16, _day: u8, _part: u8, answer: &str) -> Result<Response, Error> {
+            self.submitted.borrow_mut().push(answer.to_string());
+            Ok(match answer.parse::<i64>() {
+                Ok(n) if n == self.answer => Response::Correct,
+                Ok(n) if n > self.answer => Response::TooHigh,
+                Ok(_) => Response::TooLow,
+                Err(_) => Response::Wrong,
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let page = |text| {
+            format!(
+                "<html><main><article><p>{}</p></article></main></html>",
+                text
+            )
+        };
+        assert_eq!(
+            Response::Correct,
+            Response::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            ))
+        );
+        assert_eq!(
+            Response::TooHigh,
+            Response::parse(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+        );
+        assert_eq!(
+            Response::TooLow,
+            Response::parse(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+        );
+        assert_eq!(
+            Response::Wrong,
+            Response::parse(&page("That's not the right answer. If you're stuck, ..."))
+        );
+        assert_eq!(
+            Response::TooSoon("You gave an answer too recently. You have 37s left to wait.".into()),
+            Response::parse(&page(
+                "You gave an answer too recently. You have 37s left to wait."
+            ))
+        );
+        assert_eq!(
+            Response::WrongLevel,
+            Response::parse(&page("You don't seem to be solving the right level."))
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = std::env::temp_dir().join(format!("aoclib-submit-{}", std::process::id()));
+        let fake = FakeSubmitter {
+            answer: 500,
+            submitted: RefCell::new(Vec::new()),
+        };
+        let send = |answer| submit(&fake, &dir, 2020, 7, 2, answer, "abc123").unwrap();
+
+        let outcomes = vec![
+            send("600"),
+            send("abc"),
+            send("100"),
+            send("600"),
+            send("700"),
+            send("50"),
+            send("abc"),
+            send("500"),
+            send("400"),
+        ];
+        let submissions = fs::read_to_string(Submissions::path(&dir, 2020)).unwrap();
+        let answers = Answers::load(&dir, 2020).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            vec![
+                Outcome::Sent(Response::TooHigh),
+                Outcome::Sent(Response::Wrong),
+                Outcome::Sent(Response::TooLow),
+                Outcome::KnownWrong("600 is too high, 600 already was".into()),
+                Outcome::KnownWrong("700 is too high, 600 already was".into()),
+                Outcome::KnownWrong("50 is too low, 100 already was".into()),
+                Outcome::KnownWrong("abc was already rejected".into()),
+                Outcome::Sent(Response::Correct),
+                Outcome::AlreadyAccepted("500".into()),
+            ],
+            outcomes
+        );
+        assert_eq!(vec!["600", "abc", "100", "500"], *fake.submitted.borrow());
+        assert_eq!(Some("500"), answers.get(7, 2));
+        assert_eq!(Some("abc123"), answers.checksum(7));
+        assert_eq!(
+            Submissions::parse(&submissions).unwrap(),
+            Submissions::parse(
+                "[7.2]\nwrong = [\"abc\"]\ntoo_high = [\"600\"]\ntoo_low = [\"100\"]\n"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_submit_other_input() {
+        let dir = std::env::temp_dir().join(format!("aoclib-submit-input-{}", std::process::id()));
+        let fake = FakeSubmitter {
+            answer: 500,
+            submitted: RefCell::new(Vec::new()),
+        };
+        Answers::record(&dir, 2020, 7, 1, "42", "abc123").unwrap();
+
+        let outcome = submit(&fake, &dir, 2020, 7, 2, "500", "def456").unwrap();
+        let answers = Answers::load(&dir, 2020).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Outcome::InputMismatch("abc123".into()), outcome);
+        assert!(fake.submitted.borrow().is_empty());
+        assert_eq!(None, answers.get(7, 2));
+        assert_eq!(Some("abc123"), answers.checksum(7));
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::time::Duration;
+
+/// How the parts of each day should be timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timing {
+    /// Solve each part once without reporting how long it took
+    #[default]
+    Off,
+    /// Solve each part once and report the elapsed time
+    Once,
+    /// Solve each part the given number of times and report statistics over all runs
+    Bench(u32),
+}
+
+impl Timing {
+    /// Number of times each part should be solved
+    pub fn runs(self) -> u32 {
+        match self {
+            Timing::Off | Timing::Once => 1,
+            Timing::Bench(n) => n.max(1),
+        }
+    }
+}
+
+/// Summary of the durations of repeated runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn new(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+        let total: Duration = sorted.iter().sum();
+
+        Some(Stats {
+            runs: sorted.len(),
+            min: sorted[0],
+            median,
+            mean: total / sorted.len() as u32,
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs, min {:?} / median {:?} / mean {:?}",
+            self.runs, self.min, self.median, self.mean
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(v: &[u64]) -> Vec<Duration> {
+        v.iter().map(|&x| Duration::from_millis(x)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(None, Stats::new(&[]));
+
+        let s = Stats::new(&ms(&[5, 1, 3])).unwrap();
+        assert_eq!(3, s.runs);
+        assert_eq!(Duration::from_millis(1), s.min);
+        assert_eq!(Duration::from_millis(3), s.median);
+        assert_eq!(Duration::from_millis(3), s.mean);
+
+        let s = Stats::new(&ms(&[4, 1, 2, 9])).unwrap();
+        assert_eq!(Duration::from_millis(1), s.min);
+        assert_eq!(Duration::from_millis(3), s.median);
+        assert_eq!(Duration::from_millis(4), s.mean);
+    }
+}
@@ -0,0 +1,212 @@
+//! Compass directions, and a turtle that moves and turns on the plane
+//!
+//! Directions use the same axes as [`Point2::rotate_left`]: x grows to the east and y grows to the
+//! north. Turns are given in degrees clockwise, so negative turns go anticlockwise.
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::geom::Point2;
+
+/// Reasons a direction or turn was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectionError {
+    /// Not one of N, E, S, W, U, D, L or R
+    Unknown(String),
+    /// A turn that isn't a whole number of right angles
+    Angle(i64),
+}
+
+impl fmt::Display for DirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DirectionError::Unknown(s) => write!(f, "unknown direction {:?}", s),
+            DirectionError::Angle(degrees) => {
+                write!(f, "can't turn {} degrees, only multiples of 90", degrees)
+            }
+        }
+    }
+}
+
+impl error::Error for DirectionError {}
+
+/// The number of quarter turns clockwise in a turn of `degrees`, from 0 to 3
+fn quarter_turns(degrees: i64) -> Result<i64, DirectionError> {
+    if degrees % 90 != 0 {
+        return Err(DirectionError::Angle(degrees));
+    }
+    Ok((degrees / 90).rem_euclid(4))
+}
+
+/// Rotate `point` about the origin by `degrees` clockwise
+pub fn rotate(point: Point2, degrees: i64) -> Result<Point2, DirectionError> {
+    Ok((0..quarter_turns(degrees)?).fold(point, |p, _| p.rotate_right()))
+}
+
+/// One of the four compass directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// A single step in this direction
+    pub fn delta(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new([0, 1]),
+            Direction::East => Point2::new([1, 0]),
+            Direction::South => Point2::new([0, -1]),
+            Direction::West => Point2::new([-1, 0]),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The direction faced after turning `degrees` clockwise
+    pub fn turn(self, degrees: i64) -> Result<Self, DirectionError> {
+        Ok(Direction::ALL[(self as usize + quarter_turns(degrees)? as usize) % 4])
+    }
+}
+
+/// Parses compass points (N, E, S, W) and screen directions (U, R, D, L)
+impl TryFrom<char> for Direction {
+    type Error = DirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'N' | 'U' => Ok(Direction::North),
+            'E' | 'R' => Ok(Direction::East),
+            'S' | 'D' => Ok(Direction::South),
+            'W' | 'L' => Ok(Direction::West),
+            _ => Err(DirectionError::Unknown(c.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = DirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(DirectionError::Unknown(s.to_string())),
+        }
+    }
+}
+
+/// Something that walks the plane, facing one of the compass directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Turtle {
+    pub position: Point2,
+    pub heading: Direction,
+}
+
+impl Turtle {
+    /// A turtle at the origin, facing `heading`
+    pub fn new(heading: Direction) -> Self {
+        Turtle {
+            position: Point2::ORIGIN,
+            heading,
+        }
+    }
+
+    /// Start somewhere other than the origin
+    pub fn at(mut self, position: Point2) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Turn `degrees` clockwise, leaving the heading alone if that isn't a right angle
+    pub fn turn(&mut self, degrees: i64) -> Result<(), DirectionError> {
+        self.heading = self.heading.turn(degrees)?;
+        Ok(())
+    }
+
+    /// Move `distance` in the direction it's facing
+    pub fn forward(&mut self, distance: i64) {
+        self.step(self.heading, distance);
+    }
+
+    /// Move `distance` in `direction` without turning
+    pub fn step(&mut self, direction: Direction, distance: i64) {
+        self.position += direction.delta() * distance;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(Direction::North), "N".parse());
+        assert_eq!(Ok(Direction::North), "U".parse());
+        assert_eq!(Ok(Direction::West), Direction::try_from('L'));
+        assert_eq!(Ok(Direction::South), Direction::try_from('D'));
+        assert_eq!(
+            "unknown direction \"NE\"",
+            "NE".parse::<Direction>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(DirectionError::Unknown("x".to_string())),
+            Direction::try_from('x')
+        );
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::North, Direction::West.turn_right());
+        assert_eq!(Direction::East, Direction::West.reverse());
+        assert_eq!(Ok(Direction::South), Direction::East.turn(90));
+        assert_eq!(Ok(Direction::North), Direction::East.turn(-90));
+        assert_eq!(Ok(Direction::West), Direction::East.turn(540));
+        assert_eq!(Ok(Direction::East), Direction::East.turn(-360));
+        assert_eq!(
+            "can't turn 45 degrees, only multiples of 90",
+            Direction::East.turn(45).unwrap_err().to_string()
+        );
+
+        assert_eq!(Ok(Point2::new([1, -3])), rotate(Point2::new([3, 1]), 90));
+        assert_eq!(Ok(Point2::new([-1, 3])), rotate(Point2::new([3, 1]), -90));
+        assert_eq!(Err(DirectionError::Angle(100)), rotate(Point2::ORIGIN, 100));
+    }
+
+    #[test]
+    fn test_turtle() {
+        let mut turtle = Turtle::new(Direction::East).at(Point2::new([1, 1]));
+        turtle.forward(10);
+        turtle.step(Direction::North, 3);
+        turtle.turn(90).unwrap();
+        turtle.forward(5);
+        assert_eq!(Point2::new([11, -1]), turtle.position);
+        assert_eq!(Direction::South, turtle.heading);
+
+        assert_eq!(Err(DirectionError::Angle(30)), turtle.turn(30));
+        assert_eq!(Direction::South, turtle.heading);
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use super::cache;
+use super::day::{self, Day, DayResult};
+use super::error::Error;
+use super::options::Options;
+use super::params::Params;
+use super::source::InputSource;
+
+pub struct Year {
+    pub year: u16,
+    days: Vec<Day>,
+}
+
+impl Year {
+    pub fn new(year: u16) -> Self {
+        Year {
+            year,
+            days: Vec::new(),
+        }
+    }
+
+    pub fn day<D: Into<Day>>(mut self, d: D) -> Self {
+        self.days.push(d.into());
+        self
+    }
+
+    /// The registered days, in the order they were added
+    pub fn days(&self) -> &[Day] {
+        &self.days
+    }
+
+    pub fn run(self, day: Option<u8>, input: String, options: Options) -> Result<DayResult, Error> {
+        let d = self.find(day)?;
+        d.check_params(&options.params)?;
+        Ok(d.solve(input, options))
+    }
+
+    /// Solve a single day like [`Year::run`], fetching its input from `source`
+    pub fn run_from<S: InputSource + ?Sized>(
+        self,
+        day: Option<u8>,
+        source: &S,
+        options: Options,
+    ) -> Result<DayResult, Error> {
+        let year = self.year;
+        let d = self.find(day)?;
+        d.check_params(&options.params)?;
+        let input = source.fetch(year, d.day)?;
+        Ok(d.solve(input, options))
+    }
+
+    /// The requested day, or the latest one if no day was requested
+    fn find(self, day: Option<u8>) -> Result<Day, Error> {
+        let year = self.year;
+        let nearest = day.map(|day| nearest_days(&self.days, day));
+        let d = if let Some(day) = day {
+            self.days.into_iter().find(|x| x.day == day)
+        } else {
+            self.days.into_iter().max_by_key(|x| x.day)
+        };
+
+        d.ok_or_else(|| Error::DayNotFound {
+            year,
+            day,
+            nearest: nearest.unwrap_or_default(),
+        })
+    }
+
+    /// Check that every parameter in `params` is declared by at least one part of some day
+    pub fn check_params(&self, params: &Params) -> Result<(), Error> {
+        day::check_declared(&self.days, params)
+    }
+
+    /// Solve every registered day, reading each input from the cache in `dir`.
+    ///
+    /// Days whose input can't be read, or doesn't match its recorded checksum, are reported as an
+    /// error instead of being solved. When
+    /// `options.jobs` allows it, days are solved in parallel on the current rayon thread pool.
+    pub fn run_all(self, dir: &Path, options: Options) -> Result<YearResult, Error> {
+        self.check_params(&options.params)?;
+        Ok(self.solve_all(dir, options))
+    }
+
+    /// [`Year::run_all`] without checking the parameters, which the caller has already done
+    pub(crate) fn solve_all(self, dir: &Path, options: Options) -> YearResult {
+        let mut days = self.days;
+        days.sort_by_key(|d| d.day);
+
+        let year = self.year;
+        let solve = |d: Day| match cache::read(dir, year, d.day) {
+            Ok(input) => Ok(d.solve(input, options.clone())),
+            Err(e) => Err((d.day, e)),
+        };
+        let days = if options.jobs > 1 {
+            days.into_par_iter().map(solve).collect()
+        } else {
+            days.into_iter().map(solve).collect()
+        };
+
+        YearResult { year, days }
+    }
+}
+
+/// The registered days closest to `day`, in order. There are two when it falls halfway between.
+fn nearest_days(days: &[Day], day: u8) -> Vec<u8> {
+    let distance = |d: &Day| (i16::from(d.day) - i16::from(day)).abs();
+    let min = match days.iter().map(distance).min() {
+        Some(min) => min,
+        None => return Vec::new(),
+    };
+    let mut nearest: Vec<u8> = days
+        .iter()
+        .filter(|d| distance(d) == min)
+        .map(|d| d.day)
+        .collect();
+    nearest.sort_unstable();
+    nearest.dedup();
+    nearest
+}
+
+/// The results of solving every day of a year
+#[derive(Debug)]
+pub struct YearResult {
+    pub year: u16,
+    /// Either the day's result or the day number and the reason it was skipped
+    pub days: Vec<Result<DayResult, (u8, Error)>>,
+}